@@ -0,0 +1,48 @@
+//! Benchmarks for parsing and solving each day's puzzle input.
+//!
+//! Run with `cargo bench`, or `cargo bench -- day11` for a single day.
+
+use advent_of_code_2022::{
+    day1::Day1, day10::Day10, day11::Day11, day2::Day2, day3::Day3, day4::Day4, day5::Day5,
+    day6::Day6, day7::Day7, day8::Day8, day9::Day9, input::InputSource, solution::Puzzle,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Benchmarks parsing the day's input, and solving each part from the parsed input.
+fn bench_puzzle<P: Puzzle>(c: &mut Criterion, day: u8, puzzle: P) {
+    let input = InputSource::default()
+        .read(day)
+        .unwrap_or_else(|e| panic!("{e}"));
+    let parsed = puzzle.parse(&input).unwrap_or_else(|e| panic!("{e}"));
+
+    let mut group = c.benchmark_group(format!("day{day}"));
+    // Some parts take tens of milliseconds, so keep the number of samples down.
+    group.sample_size(10);
+
+    group.bench_function("parse", |b| b.iter(|| puzzle.parse(black_box(&input))));
+    group.bench_function("part1", |b| {
+        b.iter(|| puzzle.solve_part1(black_box(&parsed)))
+    });
+    group.bench_function("part2", |b| {
+        b.iter(|| puzzle.solve_part2(black_box(&parsed)))
+    });
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_puzzle(c, 1, Day1);
+    bench_puzzle(c, 2, Day2);
+    bench_puzzle(c, 3, Day3);
+    bench_puzzle(c, 4, Day4);
+    bench_puzzle(c, 5, Day5);
+    bench_puzzle(c, 6, Day6);
+    bench_puzzle(c, 7, Day7);
+    bench_puzzle(c, 8, Day8);
+    bench_puzzle(c, 9, Day9);
+    bench_puzzle(c, 10, Day10);
+    bench_puzzle(c, 11, Day11);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,194 @@
+//! Known answers to the puzzle inputs, used to catch regressions.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{error::Error, run, solution::Answer, PARTS};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r##"
+[day1]
+part1 = 24000
+
+[day5]
+part1 = "CMZ"
+
+[day10]
+part2 = ["#..#", ".##."]
+"##;
+
+    #[test]
+    fn parse_keeps_the_type_of_each_answer() -> Result<(), String> {
+        let known_answers = KnownAnswers::parse(ANSWERS)?;
+
+        assert_eq!(known_answers.get(1, 1), Some(&Answer::Number(24000)));
+        assert_eq!(known_answers.get(1, 2), None);
+        assert_eq!(
+            known_answers.get(5, 1),
+            Some(&Answer::Text("CMZ".to_string()))
+        );
+        assert_eq!(
+            known_answers.get(10, 2),
+            Some(&Answer::from_crt("#..#\n.##."))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rejects_unexpected_keys_and_values() {
+        assert!(KnownAnswers::parse("[dayone]\npart1 = 1").is_err());
+        assert!(KnownAnswers::parse("[day1]\npart3 = 1").is_err());
+        assert!(KnownAnswers::parse("[day1]\npart1 = 1.5").is_err());
+        assert!(KnownAnswers::parse("[day1]\npart1 = [1, 2]").is_err());
+        assert!(KnownAnswers::parse("day1 = 1").is_err());
+    }
+
+    #[test]
+    fn verify_compares_the_answer_with_the_known_answer() -> Result<(), String> {
+        let known_answers = KnownAnswers::parse("[day6]\npart1 = 7\npart2 = 20")?;
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+        assert!(matches!(
+            known_answers.verify(6, 1, input),
+            Verdict::Pass(_)
+        ));
+        assert!(matches!(
+            known_answers.verify(6, 2, input),
+            Verdict::Fail {
+                actual: Answer::Number(19),
+                ..
+            }
+        ));
+        assert!(matches!(
+            KnownAnswers::default().verify(6, 1, input),
+            Verdict::Unknown(_)
+        ));
+        assert!(matches!(
+            known_answers.verify(6, 1, "abc"),
+            Verdict::Error(_)
+        ));
+
+        Ok(())
+    }
+}
+
+/// The file of known answers for this crate's own puzzle inputs.
+pub const ANSWERS_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+/// The verified answers to each day and part.
+///
+/// The answers are kept in a TOML file with a `[dayN]` table for each day and a `partN` key for
+/// each part. Numeric answers are integers, text answers are strings, and CRT answers are arrays
+/// with one string per row.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    /// Answers keyed by day and part.
+    answers: BTreeMap<(u8, u8), Answer>,
+}
+
+/// The outcome of checking a solution against its known answer.
+#[derive(Debug)]
+pub enum Verdict {
+    /// The solution produced the known answer.
+    Pass(Answer),
+
+    /// The solution produced a different answer.
+    Fail {
+        /// The known answer.
+        expected: Answer,
+
+        /// The answer that the solution produced.
+        actual: Answer,
+    },
+
+    /// The solution produced an answer, but there is no known answer to check it against.
+    Unknown(Answer),
+
+    /// The solution failed to produce an answer.
+    Error(Error),
+}
+
+impl KnownAnswers {
+    /// Loads the known answers from a TOML file.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let known_answers_error = |reason: String| Error::KnownAnswers {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let text = fs::read_to_string(path).map_err(|e| known_answers_error(e.to_string()))?;
+
+        Self::parse(&text).map_err(known_answers_error)
+    }
+
+    /// Gets the known answer for the given day and part, if there is one.
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    /// Runs the solution for the given day and part and checks it against the known answer.
+    pub fn verify(&self, day: u8, part: u8, input: &str) -> Verdict {
+        match (run(day, part, input), self.get(day, part)) {
+            (Err(e), _) => Verdict::Error(e),
+            (Ok(actual), None) => Verdict::Unknown(actual),
+            (Ok(actual), Some(expected)) if actual == *expected => Verdict::Pass(actual),
+            (Ok(actual), Some(expected)) => Verdict::Fail {
+                expected: expected.clone(),
+                actual,
+            },
+        }
+    }
+
+    /// Parses the known answers from the contents of a TOML file.
+    fn parse(text: &str) -> Result<Self, String> {
+        let table = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut answers = BTreeMap::new();
+
+        for (day_key, parts) in table {
+            let day = parse_key(&day_key, "day")?;
+            let toml::Value::Table(parts) = parts else {
+                return Err(format!("{day_key} must be a table of parts"));
+            };
+
+            for (part_key, value) in parts {
+                let part = parse_key(&part_key, "part")?;
+                if !PARTS.contains(&part) {
+                    return Err(format!("{day_key}.{part_key} is not a part of a puzzle"));
+                }
+                let answer = parse_answer(value)
+                    .ok_or_else(|| format!("{day_key}.{part_key} is not a valid answer"))?;
+
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(KnownAnswers { answers })
+    }
+}
+
+/// Parses a key such as `day7` into its number.
+fn parse_key(key: &str, prefix: &str) -> Result<u8, String> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| format!("expected a key like {prefix}1, found {key}"))
+}
+
+/// Converts a TOML value into an answer, keeping its type.
+fn parse_answer(value: toml::Value) -> Option<Answer> {
+    match value {
+        toml::Value::Integer(n) => Some(n.into()),
+        toml::Value::String(s) => Some(s.into()),
+        toml::Value::Array(rows) => rows
+            .into_iter()
+            .map(|row| match row {
+                toml::Value::String(row) => Some(row),
+                _ => None,
+            })
+            .collect::<Option<Vec<String>>>()
+            .map(Answer::Crt),
+        _ => None,
+    }
+}
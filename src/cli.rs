@@ -0,0 +1,524 @@
+//! Command line parsing for the Advent of Code 2022 runner.
+
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use advent_of_code_2022::{
+    answers::ANSWERS_FILE, day5::Animation, input::InputSource, DAYS, PARTS,
+};
+use anyhow::{bail, Context, Error};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_returns_a_single_day_and_part() -> Result<(), Error> {
+        let command = Command::parse(args("run --day 7 --part 2"))?;
+
+        assert_eq!(
+            command,
+            Command::Run(RunOptions {
+                selection: Selection::Day {
+                    day: 7,
+                    part: Some(2)
+                },
+                input: InputSource::Default,
+                format: Format::Text,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_returns_all() -> Result<(), Error> {
+        assert_eq!(
+            Command::parse(args("run --all --format json"))?,
+            Command::Run(RunOptions {
+                selection: Selection::All,
+                input: InputSource::Default,
+                format: Format::Json,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_returns_the_input_source() -> Result<(), Error> {
+        let Command::Run(options) = Command::parse(args("run --day 1 --input -"))? else {
+            panic!("Expected a run command");
+        };
+
+        assert_eq!(options.input, InputSource::Stdin);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rejects_invalid_options() {
+        assert!(Command::parse(args("run")).is_err());
+        assert!(Command::parse(args("run --part 1")).is_err());
+        assert!(Command::parse(args("run --all --day 1")).is_err());
+        assert!(Command::parse(args("run --day seven")).is_err());
+        assert!(Command::parse(args("walk --day 1")).is_err());
+        assert!(Command::parse(args("run --all --input day1.txt")).is_err());
+        assert!(Command::parse(args("run --day 1 --input")).is_err());
+        assert!(Command::parse(args("run --day 1 --answers answers.toml")).is_err());
+        assert!(Command::parse(args("verify --input day1.txt")).is_err());
+        assert!(Command::parse(args("run --day 1 --runs 5")).is_err());
+        assert!(Command::parse(args("bench --runs 0")).is_err());
+        assert!(Command::parse(args("run --all --format yaml")).is_err());
+        assert!(Command::parse(args("verify --format json")).is_err());
+        assert!(Command::parse(args("run --day 5 --highlight")).is_err());
+        assert!(Command::parse(args("bench --delay-ms 10")).is_err());
+        assert!(Command::parse(args("animate --day 5")).is_err());
+        assert!(Command::parse(args("animate --day 1 --part 1")).is_err());
+        assert!(Command::parse(args("animate --day 5 --part 3")).is_err());
+        assert!(Command::parse(args("animate --day 5 --part 1 --format json")).is_err());
+    }
+
+    #[test]
+    fn parse_returns_the_animation_options() -> Result<(), Error> {
+        assert_eq!(
+            Command::parse(args("animate --day 5 --part 2 --delay-ms 50 --highlight"))?,
+            Command::Animate(AnimateOptions {
+                part: 2,
+                input: InputSource::Default,
+                animation: Animation {
+                    delay: Duration::from_millis(50),
+                    highlight: true,
+                },
+            })
+        );
+
+        let Command::Animate(options) = Command::parse(args("animate --day 5 --part 1"))? else {
+            panic!("Expected an animate command");
+        };
+
+        assert_eq!(options.animation.delay, DEFAULT_DELAY);
+        assert!(!options.animation.highlight);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_returns_the_number_of_bench_runs() -> Result<(), Error> {
+        assert_eq!(
+            Command::parse(args("bench --day 11 --part 2 --runs 3"))?,
+            Command::Bench(BenchOptions {
+                selection: Selection::Day {
+                    day: 11,
+                    part: Some(2)
+                },
+                input: InputSource::Default,
+                runs: 3,
+            })
+        );
+
+        let Command::Bench(options) = Command::parse(args("bench"))? else {
+            panic!("Expected a bench command");
+        };
+
+        assert_eq!(options.selection, Selection::All);
+        assert_eq!(options.runs, DEFAULT_RUNS);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_verifies_everything_by_default() -> Result<(), Error> {
+        assert_eq!(
+            Command::parse(args("verify"))?,
+            Command::Verify(VerifyOptions {
+                selection: Selection::All,
+                answers: PathBuf::from(ANSWERS_FILE),
+            })
+        );
+
+        let Command::Verify(options) = Command::parse(args("verify --day 3 --answers mine.toml"))?
+        else {
+            panic!("Expected a verify command");
+        };
+
+        assert_eq!(options.selection.days(), vec![3]);
+        assert_eq!(options.selection.parts(), vec![1, 2]);
+        assert_eq!(options.answers, PathBuf::from("mine.toml"));
+
+        Ok(())
+    }
+}
+
+/// Usage information for the runner.
+pub const USAGE: &str = "Usage:
+    advent-of-code-2022 run --day <DAY> [--part <PART>] [--input <PATH>] [--format <FORMAT>]
+    advent-of-code-2022 run --all [--format <FORMAT>]
+    advent-of-code-2022 verify [--day <DAY> [--part <PART>]] [--answers <PATH>]
+    advent-of-code-2022 bench [--day <DAY> [--part <PART>]] [--input <PATH>] [--runs <RUNS>]
+    advent-of-code-2022 animate --day 5 --part <PART> [--input <PATH>] [--delay-ms <MS>] [--highlight]
+    advent-of-code-2022 help
+
+Input is read from --input (use - for stdin), otherwise from dayN.txt in the
+directory named by AOC_INPUT_DIR, otherwise from this crate's src/data.
+
+run prints answers as text by default. --format json prints one JSON object per
+line instead, with the day, part, answer, timing (in nanoseconds) and error.
+
+verify checks every selected day and part against the known answers in
+--answers, otherwise in this crate's answers.toml.
+
+bench runs every selected day and part --runs times (10 by default) and reports
+the min/median/max parse and solve times.
+
+animate redraws the day 5 crates after each move, waiting --delay-ms between
+moves (200 by default). --highlight marks the crates moved by each instruction.";
+
+/// The number of times `bench` runs each solution by default.
+pub const DEFAULT_RUNS: usize = 10;
+
+/// How long `animate` shows the crates after each move by default.
+pub const DEFAULT_DELAY: Duration = Duration::from_millis(200);
+
+/// A command given to the runner.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Runs the selected solutions.
+    Run(RunOptions),
+
+    /// Checks the selected solutions against their known answers.
+    Verify(VerifyOptions),
+
+    /// Times repeated runs of the selected solutions.
+    Bench(BenchOptions),
+
+    /// Animates the crates being rearranged on day 5.
+    Animate(AnimateOptions),
+
+    /// Prints usage information.
+    Help,
+}
+
+/// Options for the `run` command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Which solutions to run.
+    pub selection: Selection,
+
+    /// Where to read the puzzle input from.
+    pub input: InputSource,
+
+    /// How to print the answers.
+    pub format: Format,
+}
+
+/// How the `run` command prints answers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable text.
+    #[default]
+    Text,
+
+    /// One JSON object per day and part.
+    Json,
+}
+
+/// Options for the `verify` command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VerifyOptions {
+    /// Which solutions to verify.
+    pub selection: Selection,
+
+    /// The file of known answers.
+    pub answers: PathBuf,
+}
+
+/// Options for the `bench` command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Which solutions to time.
+    pub selection: Selection,
+
+    /// Where to read the puzzle input from.
+    pub input: InputSource,
+
+    /// How many times to run each solution.
+    pub runs: usize,
+}
+
+/// Options for the `animate` command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AnimateOptions {
+    /// Which part's crane to animate.
+    pub part: u8,
+
+    /// Where to read the puzzle input from.
+    pub input: InputSource,
+
+    /// How to draw each move.
+    pub animation: Animation,
+}
+
+/// Which solutions to run.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// Every part of every day.
+    All,
+
+    /// A single day, optionally limited to one of its parts.
+    Day {
+        /// The day to run.
+        day: u8,
+
+        /// The part to run, or both parts when `None`.
+        part: Option<u8>,
+    },
+}
+
+/// The options given to a command, before they are checked against what the command accepts.
+#[derive(Debug, Default)]
+struct Options {
+    all: bool,
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    answers: Option<String>,
+    runs: Option<usize>,
+    format: Option<Format>,
+    delay_ms: Option<u64>,
+    highlight: bool,
+}
+
+impl Command {
+    /// Parses a command from the arguments passed to the program (excluding the program name).
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, Error> {
+        let mut args = args.into_iter();
+
+        match args.next().as_deref() {
+            None | Some("help" | "--help" | "-h") => Ok(Command::Help),
+            Some("run") => Ok(Command::Run(RunOptions::parse(args)?)),
+            Some("verify") => Ok(Command::Verify(VerifyOptions::parse(args)?)),
+            Some("bench") => Ok(Command::Bench(BenchOptions::parse(args)?)),
+            Some("animate") => Ok(Command::Animate(AnimateOptions::parse(args)?)),
+            Some(other) => bail!("Unknown command: {other}\n\n{USAGE}"),
+        }
+    }
+}
+
+impl RunOptions {
+    /// Parses the options for the `run` command.
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, Error> {
+        let options = Options::parse(args)?;
+
+        if options.answers.is_some() {
+            bail!("--answers can only be used with verify.");
+        }
+        if options.runs.is_some() {
+            bail!("--runs can only be used with bench.");
+        }
+        options.reject_animation()?;
+
+        let selection = match options.selection()? {
+            Some(selection) => selection,
+            None => bail!("Either --day or --all is required.\n\n{USAGE}"),
+        };
+        let input = options.input_source(&selection)?;
+
+        Ok(RunOptions {
+            selection,
+            input,
+            format: options.format.unwrap_or_default(),
+        })
+    }
+}
+
+impl BenchOptions {
+    /// Parses the options for the `bench` command.
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, Error> {
+        let options = Options::parse(args)?;
+
+        if options.answers.is_some() {
+            bail!("--answers can only be used with verify.");
+        }
+        if options.format.is_some() {
+            bail!("--format can only be used with run.");
+        }
+        options.reject_animation()?;
+
+        let selection = options.selection()?.unwrap_or(Selection::All);
+        let input = options.input_source(&selection)?;
+        let runs = match options.runs {
+            Some(0) => bail!("--runs must be at least 1."),
+            Some(runs) => runs,
+            None => DEFAULT_RUNS,
+        };
+
+        Ok(BenchOptions {
+            selection,
+            input,
+            runs,
+        })
+    }
+}
+
+impl VerifyOptions {
+    /// Parses the options for the `verify` command.
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, Error> {
+        let options = Options::parse(args)?;
+
+        if options.input.is_some() {
+            bail!("--input cannot be used with verify; the known answers are for our own inputs.");
+        }
+        if options.runs.is_some() {
+            bail!("--runs can only be used with bench.");
+        }
+        if options.format.is_some() {
+            bail!("--format can only be used with run.");
+        }
+        options.reject_animation()?;
+
+        Ok(VerifyOptions {
+            selection: options.selection()?.unwrap_or(Selection::All),
+            answers: options
+                .answers
+                .map_or_else(|| PathBuf::from(ANSWERS_FILE), PathBuf::from),
+        })
+    }
+}
+
+impl AnimateOptions {
+    /// Parses the options for the `animate` command.
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, Error> {
+        let options = Options::parse(args)?;
+
+        if options.answers.is_some() {
+            bail!("--answers can only be used with verify.");
+        }
+        if options.runs.is_some() {
+            bail!("--runs can only be used with bench.");
+        }
+        if options.format.is_some() {
+            bail!("--format can only be used with run.");
+        }
+
+        let Some(selection @ Selection::Day { day: 5, part }) = options.selection()? else {
+            bail!("Only day 5 can be animated; use --day 5.\n\n{USAGE}");
+        };
+        let part = match part {
+            Some(part) if PARTS.contains(&part) => part,
+            Some(part) => bail!("Day 5 has no part {part}."),
+            None => bail!("--part is required to animate day 5."),
+        };
+        let input = options.input_source(&selection)?;
+
+        Ok(AnimateOptions {
+            part,
+            input,
+            animation: Animation {
+                delay: options
+                    .delay_ms
+                    .map_or(DEFAULT_DELAY, Duration::from_millis),
+                highlight: options.highlight,
+            },
+        })
+    }
+}
+
+impl Selection {
+    /// Gets the selected days.
+    pub fn days(&self) -> Vec<u8> {
+        match self {
+            Selection::All => DAYS.collect(),
+            Selection::Day { day, .. } => vec![*day],
+        }
+    }
+
+    /// Gets the selected parts of each day.
+    pub fn parts(&self) -> Vec<u8> {
+        match self {
+            Selection::Day {
+                part: Some(part), ..
+            } => vec![*part],
+            _ => PARTS.collect(),
+        }
+    }
+}
+
+impl Options {
+    /// Parses every option given to a command.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, Error> {
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--all" => options.all = true,
+                "--day" | "-d" => options.day = Some(parse_number(&arg, args.next())?),
+                "--part" | "-p" => options.part = Some(parse_number(&arg, args.next())?),
+                "--input" | "-i" => options.input = Some(parse_value(&arg, args.next())?),
+                "--answers" => options.answers = Some(parse_value(&arg, args.next())?),
+                "--runs" | "-n" => options.runs = Some(parse_number(&arg, args.next())?),
+                "--delay-ms" => options.delay_ms = Some(parse_number(&arg, args.next())?),
+                "--highlight" => options.highlight = true,
+                "--format" | "-f" => {
+                    options.format = Some(match parse_value(&arg, args.next())?.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        other => bail!("Unknown format: {other}; expected text or json."),
+                    })
+                }
+                _ => bail!("Unknown option: {arg}\n\n{USAGE}"),
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Fails if any option that only `animate` accepts was given.
+    fn reject_animation(&self) -> Result<(), Error> {
+        if self.delay_ms.is_some() || self.highlight {
+            bail!("--delay-ms and --highlight can only be used with animate.");
+        }
+
+        Ok(())
+    }
+
+    /// Gets the selected solutions, or `None` if nothing was selected.
+    fn selection(&self) -> Result<Option<Selection>, Error> {
+        Ok(match (self.all, self.day, self.part) {
+            (true, None, None) => Some(Selection::All),
+            (true, _, _) => bail!("--all cannot be combined with --day or --part."),
+            (false, Some(day), part) => Some(Selection::Day { day, part }),
+            (false, None, Some(_)) => bail!("--part requires --day."),
+            (false, None, None) => None,
+        })
+    }
+
+    /// Gets where to read the puzzle input from for the selected solutions.
+    fn input_source(&self, selection: &Selection) -> Result<InputSource, Error> {
+        match (&self.input, selection) {
+            (None, _) => Ok(InputSource::Default),
+            (Some(_), Selection::All) => {
+                bail!("--input cannot be combined with --all; each day reads its own input.")
+            }
+            (Some(path), Selection::Day { .. }) => Ok(InputSource::from_arg(path)),
+        }
+    }
+}
+
+/// Parses the value of an option.
+fn parse_value(option: &str, value: Option<String>) -> Result<String, Error> {
+    value.with_context(|| format!("{option} is missing its value."))
+}
+
+/// Parses the value of a numeric option.
+fn parse_number<T: FromStr>(option: &str, value: Option<String>) -> Result<T, Error> {
+    let value = parse_value(option, value)?;
+
+    value
+        .parse::<T>()
+        .ok()
+        .with_context(|| format!("Invalid value for {option}: {value}"))
+}
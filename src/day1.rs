@@ -3,6 +3,22 @@ mod tests {
 
     use super::*;
 
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
     #[test]
     fn get_most_calories_should_return_the_largest_sum_of_calories() {
         let input = "1000
@@ -22,7 +38,7 @@ mod tests {
 
 ";
 
-        let elf_with_most = get_most_calories(input).unwrap();
+        let elf_with_most = get_most_calories(input.as_bytes()).unwrap().unwrap();
 
         assert_eq!(elf_with_most.calories, 24000);
     }
@@ -46,137 +62,459 @@ mod tests {
 
 ";
 
-        let top_elves: Vec<Elf> = get_top_elves(input, 3);
+        let top_elves: Vec<Elf> = get_top_elves(input.as_bytes(), 3).unwrap();
 
         assert_eq!(top_elves.len(), 3);
-        assert_eq!(top_elves.get(0).unwrap().calories, 24000);
+        assert_eq!(top_elves.first().unwrap().calories, 24000);
         assert_eq!(top_elves.get(1).unwrap().calories, 11000);
         assert_eq!(top_elves.get(2).unwrap().calories, 10000);
     }
+
+    #[test]
+    fn elf_iter_should_handle_crlf_line_endings_and_extra_blank_lines() {
+        let input = "\r\n1000\r\n2000\r\n\r\n\r\n4000\r\n";
+
+        let elves = ElfIter::new(input.as_bytes())
+            .collect::<Result<Vec<Elf>, Error>>()
+            .unwrap();
+
+        assert_eq!(
+            elves,
+            vec![
+                Elf {
+                    ordinal: 1,
+                    calories: 3000,
+                    items: vec![1000, 2000],
+                },
+                Elf {
+                    ordinal: 2,
+                    calories: 4000,
+                    items: vec![4000],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn get_top_elves_should_identify_each_elf() {
+        let top_elves = get_top_elves(EXAMPLE.as_bytes(), 2).unwrap();
+
+        assert_eq!(top_elves[0].ordinal, 4);
+        assert_eq!(top_elves[0].item_count(), 3);
+        assert_eq!(top_elves[1].ordinal, 3);
+        assert_eq!(top_elves[1].items, vec![5000, 6000]);
+    }
+
+    #[test]
+    fn get_top_elves_should_keep_the_first_listed_of_tied_elves() {
+        let input = "100\n\n300\n\n200\n\n300\n\n200\n";
+
+        let ordinals = |top_n| {
+            get_top_elves(input.as_bytes(), top_n)
+                .unwrap()
+                .iter()
+                .map(|elf| elf.ordinal)
+                .collect::<Vec<usize>>()
+        };
+
+        assert_eq!(ordinals(0), Vec::<usize>::new());
+        assert_eq!(ordinals(3), vec![2, 4, 3]);
+        assert_eq!(ordinals(5000), vec![2, 4, 3, 5, 1]);
+    }
+
+    #[test]
+    fn elf_iter_should_report_calories_that_overflow() {
+        let input = "18446744073709551615\n1\n";
+
+        let error = get_most_calories(input.as_bytes()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 2: the elf's total calories overflow a u64: \"1\""
+        );
+    }
+
+    #[test]
+    fn get_leaderboard_should_share_ranks_between_tied_elves() {
+        let input = "300\n\n500\n\n200\n100\n\n500\n\n100\n";
+
+        let leaderboard = get_leaderboard(input.as_bytes()).unwrap();
+
+        let places = leaderboard
+            .iter()
+            .map(|ranking| (ranking.rank, ranking.elf.ordinal, ranking.is_tied))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            places,
+            vec![
+                (1, 2, true),
+                (1, 4, true),
+                (3, 1, true),
+                (3, 3, true),
+                (5, 5, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn calorie_statistics_should_summarize_the_elves() {
+        let statistics = CalorieStatistics::from_calorie_list(EXAMPLE.as_bytes()).unwrap();
+
+        assert_eq!(statistics.count(), 5);
+        assert_eq!(statistics.total(), 55000);
+        assert_eq!(statistics.mean(), Some(11000.0));
+        assert_eq!(statistics.median(), Some(10000.0));
+        assert_eq!(statistics.percentile(0.0), Some(4000.0));
+        assert_eq!(statistics.percentile(75.0), Some(11000.0));
+        assert_eq!(statistics.percentile(100.0), Some(24000.0));
+        assert_eq!(statistics.percentile(101.0), None);
+        assert_eq!(CalorieStatistics::from_elves(&[]).median(), None);
+    }
+
+    #[test]
+    fn get_most_calories_should_report_the_line_that_is_not_a_number() {
+        let input = "1000
+2000
+
+three thousand
+";
+
+        let error = get_most_calories(input.as_bytes()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::Parse {
+                day: 1,
+                line_number: 4,
+                ..
+            }
+        ));
+    }
 }
 
-use std::env;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    io::BufRead,
+};
+
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
 
 pub mod part1 {
     use super::*;
 
-    pub fn solution() {
-        let filename = env::current_dir().unwrap().join("src/data/day1.txt");
-        let input = std::fs::read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(1)?;
+
+        let answer = Day1.part1(&input)?;
 
-        let elf_with_most = get_most_calories(&input).unwrap();
+        println!("most calories = {answer}");
 
-        println!("{elf_with_most:#?}");
+        Ok(())
     }
 }
 
 pub mod part2 {
     use super::*;
 
-    pub fn solution() {
-        let filename = env::current_dir().unwrap().join("src/data/day1.txt");
-        let input = std::fs::read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(1)?;
+
+        let answer = Day1.part2(&input)?;
+
+        println!("total calories = {answer}");
 
-        let top_elves = get_top_elves(&input, 3);
-        let total_calories = top_elves
+        Ok(())
+    }
+}
+
+/// Calorie Counting.
+pub struct Day1;
+
+impl Puzzle for Day1 {
+    type Input<'a> = Vec<Elf>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Elf>, Error> {
+        ElfIter::new(input.as_bytes()).collect()
+    }
+
+    fn solve_part1(&self, elves: &Self::Input<'_>) -> Result<Answer, Error> {
+        let most_calories = elves
             .iter()
             .map(|elf| elf.calories)
-            .reduce(|sum, calories| sum + calories)
-            .unwrap();
+            .max()
+            .ok_or_else(|| Error::unsolvable(1, "there are no elves in the calorie list"))?;
 
-        println!("{top_elves:#?}");
-        println!("total calories = {}", total_calories);
+        Ok(most_calories.into())
+    }
+
+    fn solve_part2(&self, elves: &Self::Input<'_>) -> Result<Answer, Error> {
+        let total_calories = top_elves(elves, 3)
+            .iter()
+            .try_fold(0u64, |total, elf| total.checked_add(elf.calories))
+            .ok_or_else(|| Error::unsolvable(1, "the total calories overflow a u64"))?;
+
+        Ok(total_calories.into())
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Elf {
-    pub calories: i32,
+    /// The elf's position in the calorie list, starting at 1.
+    pub ordinal: usize,
+    pub calories: u64,
+    /// The calories of each item the elf is carrying, in the order they were listed.
+    pub items: Vec<u64>,
 }
 
 impl Elf {
-    pub fn from(calorie_list: &str) -> (Elf, &str) {
-        let mut elf = Elf { calories: 0 };
-        let mut offset = 0;
-
-        for line in calorie_list.lines() {
-            if line.is_empty() {
-                // Empty lines consume a newline character
-                offset += 1;
-                break;
+    pub fn item_count(&self) -> usize {
+        self.items.len()
+    }
+}
+
+/// An elf's place on the leaderboard.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Ranking {
+    /// The elf's rank, starting at 1. Tied elves share a rank, and the rank after a tie skips
+    /// the places taken by the tied elves (e.g. 1, 2, 2, 4).
+    pub rank: usize,
+    /// Whether another elf is carrying exactly the same number of calories.
+    pub is_tied: bool,
+    pub elf: Elf,
+}
+
+/// Summary statistics of the calories carried by each elf.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalorieStatistics {
+    /// The calories carried by each elf, from least to most.
+    sorted_calories: Vec<u64>,
+}
+
+/// Iterates over the elves in a calorie list, reading it one line at a time.
+///
+/// Elves are separated by one or more blank lines, and lines may end with `\n` or `\r\n`.
+pub struct ElfIter<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    num_elves: usize,
+}
+
+impl<R: BufRead> ElfIter<R> {
+    pub fn new(reader: R) -> Self {
+        ElfIter {
+            reader,
+            line: String::new(),
+            line_number: 0,
+            num_elves: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ElfIter<R> {
+    type Item = Result<Elf, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut elf: Option<Elf> = None;
+
+        loop {
+            self.line.clear();
+            self.line_number += 1;
+
+            let num_bytes = match self.reader.read_line(&mut self.line) {
+                Ok(num_bytes) => num_bytes,
+                Err(source) => {
+                    return Some(Err(Error::Read {
+                        day: 1,
+                        line_number: self.line_number,
+                        source,
+                    }))
+                }
+            };
+
+            let line = self.line.trim_end_matches(['\n', '\r']);
+
+            if num_bytes == 0 || line.is_empty() {
+                match elf {
+                    // The end of the list, or a blank line between elves.
+                    Some(elf) => return Some(Ok(elf)),
+                    None if num_bytes == 0 => return None,
+                    // Extra blank lines don't start a new elf.
+                    None => continue,
+                }
             }
 
-            // +1 because each line consumes a newline character
-            // (except maybe the last one, but it's fine to act as if it is)
-            offset += line.len() + 1;
+            let calories = match line.parse::<u64>() {
+                Ok(calories) => calories,
+                Err(e) => return Some(Err(Error::parse(1, self.line_number, line, e))),
+            };
 
-            let calories = match line.parse::<i32>() {
-                Ok(c) => c,
-                Err(e) => {
-                    println!("{e:#?}");
-                    continue;
+            let elf = elf.get_or_insert_with(|| {
+                self.num_elves += 1;
+                Elf {
+                    ordinal: self.num_elves,
+                    calories: 0,
+                    items: vec![],
+                }
+            });
+            elf.calories = match elf.calories.checked_add(calories) {
+                Some(total) => total,
+                None => {
+                    return Some(Err(Error::parse(
+                        1,
+                        self.line_number,
+                        line,
+                        "the elf's total calories overflow a u64",
+                    )))
                 }
             };
+            elf.items.push(calories);
+        }
+    }
+}
+
+pub fn get_most_calories(calorie_list: impl BufRead) -> Result<Option<Elf>, Error> {
+    ElfIter::new(calorie_list).try_fold(None, |elf_with_most: Option<Elf>, current_elf| {
+        let current_elf = current_elf?;
+
+        Ok(match elf_with_most {
+            Some(elf) if elf.calories >= current_elf.calories => Some(elf),
+            _ => Some(current_elf),
+        })
+    })
+}
 
-            elf.calories += calories;
+pub fn get_top_elves(calorie_list: impl BufRead, top_n: usize) -> Result<Vec<Elf>, Error> {
+    let elves = ElfIter::new(calorie_list).collect::<Result<Vec<Elf>, Error>>()?;
+
+    Ok(top_elves(&elves, top_n).into_iter().cloned().collect())
+}
+
+/// The `top_n` elves carrying the most calories, from most to least. Of tied elves, the one
+/// listed first comes first.
+pub fn top_elves(elves: &[Elf], top_n: usize) -> Vec<&Elf> {
+    // A min-heap of the top elves so far, so the smallest of them is the one to drop.
+    let mut top_elves = BinaryHeap::new();
+    for elf in elves {
+        top_elves.push(Reverse(ByCalories(elf)));
+        if top_elves.len() > top_n {
+            top_elves.pop();
         }
+    }
+
+    top_elves
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(ByCalories(elf))| elf)
+        .collect()
+}
+
+/// Orders elves by the calories they're carrying, putting the elf listed first ahead on a tie.
+struct ByCalories<'a>(&'a Elf);
+
+impl Ord for ByCalories<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .calories
+            .cmp(&other.0.calories)
+            .then_with(|| other.0.ordinal.cmp(&self.0.ordinal))
+    }
+}
+
+impl PartialOrd for ByCalories<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        (elf, &calorie_list[offset..])
+impl PartialEq for ByCalories<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-pub fn get_most_calories(calorie_list: &str) -> Option<Elf> {
-    let mut elf_with_most: Option<Elf> = None;
-    let mut remaining_calorie_list = calorie_list;
+impl Eq for ByCalories<'_> {}
+
+/// Ranks every elf by the calories they're carrying, from most to least.
+///
+/// Tied elves share a rank and are listed in the order they appear in the calorie list.
+pub fn get_leaderboard(calorie_list: impl BufRead) -> Result<Vec<Ranking>, Error> {
+    let mut elves = ElfIter::new(calorie_list).collect::<Result<Vec<Elf>, Error>>()?;
 
-    loop {
-        let (current_elf, calorie_list) = Elf::from(remaining_calorie_list);
-        remaining_calorie_list = calorie_list;
+    // A stable sort keeps tied elves in calorie list order.
+    elves.sort_by_key(|elf| Reverse(elf.calories));
 
-        elf_with_most = match elf_with_most {
-            None => Some(current_elf),
-            Some(elf) if elf.calories < current_elf.calories => Some(current_elf),
-            Some(_) => elf_with_most,
+    let mut rankings: Vec<Ranking> = Vec::with_capacity(elves.len());
+
+    for (i, elf) in elves.into_iter().enumerate() {
+        let (rank, is_tied) = match rankings.last_mut() {
+            Some(previous) if previous.elf.calories == elf.calories => {
+                previous.is_tied = true;
+                (previous.rank, true)
+            }
+            _ => (i + 1, false),
         };
 
-        if calorie_list.is_empty() {
-            break;
-        }
+        rankings.push(Ranking { rank, is_tied, elf });
     }
 
-    elf_with_most
+    Ok(rankings)
 }
 
-pub fn get_top_elves(calorie_list: &str, top_n: usize) -> Vec<Elf> {
-    let mut elves = Vec::<Elf>::new();
+impl CalorieStatistics {
+    pub fn from_calorie_list(calorie_list: impl BufRead) -> Result<Self, Error> {
+        let elves = ElfIter::new(calorie_list).collect::<Result<Vec<Elf>, Error>>()?;
 
-    let index_of_smaller_elf = |elves: &Vec<Elf>, calories| {
-        for (i, elf) in elves.iter().enumerate() {
-            if calories > elf.calories {
-                return i;
-            }
-        }
-        elves.len()
-    };
+        Ok(Self::from_elves(&elves))
+    }
 
-    let mut remaining_calorie_list = calorie_list;
+    pub fn from_elves(elves: &[Elf]) -> Self {
+        let mut sorted_calories = elves.iter().map(|elf| elf.calories).collect::<Vec<u64>>();
+        sorted_calories.sort();
 
-    loop {
-        let (current_elf, calorie_list) = Elf::from(remaining_calorie_list);
-        remaining_calorie_list = calorie_list;
+        CalorieStatistics { sorted_calories }
+    }
 
-        let insert_index = index_of_smaller_elf(&elves, current_elf.calories);
-        if insert_index < top_n {
-            elves.insert(insert_index, current_elf);
-            if elves.len() > top_n {
-                elves.pop();
-            }
-        }
+    /// The number of elves.
+    pub fn count(&self) -> usize {
+        self.sorted_calories.len()
+    }
 
-        if calorie_list.is_empty() {
-            break;
+    /// The calories carried by all of the elves together.
+    pub fn total(&self) -> u128 {
+        self.sorted_calories.iter().map(|&c| u128::from(c)).sum()
+    }
+
+    /// The mean calories carried by an elf, or `None` if there are no elves.
+    pub fn mean(&self) -> Option<f64> {
+        match self.count() {
+            0 => None,
+            count => Some(self.total() as f64 / count as f64),
         }
     }
 
-    elves
+    /// The median calories carried by an elf, or `None` if there are no elves.
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// The calories below which `percentile` percent of the elves fall, interpolating between
+    /// the two nearest elves. Returns `None` if there are no elves or `percentile` isn't within
+    /// 0 to 100.
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        if !(0.0..=100.0).contains(&percentile) {
+            return None;
+        }
+
+        let position = percentile / 100.0 * (self.count().checked_sub(1)? as f64);
+        let lower = *self.sorted_calories.get(position.floor() as usize)? as f64;
+        let upper = *self.sorted_calories.get(position.ceil() as usize)? as f64;
+
+        Some(lower + (upper - lower) * position.fract())
+    }
 }
@@ -2,7 +2,11 @@
 #![warn(missing_docs)]
 #![warn(clippy::unwrap_used)]
 
-use anyhow::{anyhow, bail, Context, Error};
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
 
 #[cfg(test)]
 mod tests {
@@ -347,24 +351,34 @@ noop
 
         Ok(())
     }
+
+    #[test]
+    fn parse_reports_the_line_with_an_invalid_instruction() {
+        let input = "
+noop
+addx five
+";
+
+        let Err(error) = CPU::parse(input) else {
+            panic!("Expected an error");
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "day 10, line 3: invalid addx argument: \"addx five\""
+        );
+    }
 }
 
 /// Day 10, Part 1
 pub mod part1 {
-    use std::{env::current_dir, fs::read_to_string};
-
     use super::*;
 
     /// Solution for day 10, part 1.
     pub fn solution() -> Result<(), Error> {
-        let filename = current_dir()
-            .context("std::env::current_dir() failed")?
-            .join("src/data/day10.txt");
-        let input = read_to_string(filename).context("std::fs::read_to_string() failed")?;
-        let cycles = vec![20, 60, 100, 140, 180, 220];
-        let cpu = CPU::parse(&input)?;
+        let input = InputSource::default().read(10)?;
 
-        let answer = cpu.get_sum_of_signal_strengths_at_cycles(&cycles)?;
+        let answer = Day10.part1(&input)?;
 
         println!("Day 10 Part 1 = {answer}");
 
@@ -374,17 +388,38 @@ pub mod part1 {
 
 /// Day 10, Part 2
 pub mod part2 {
-    use std::{env::current_dir, fs::read_to_string};
-
     use super::*;
 
     /// Solution for day 10, part 2.
     pub fn solution() -> Result<(), Error> {
-        let filename = current_dir()
-            .context("std::env::current_dir() failed")?
-            .join("src/data/day10.txt");
-        let input = read_to_string(filename).context("std::fs::read_to_string() failed")?;
-        let cpu = CPU::parse(&input)?;
+        let input = InputSource::default().read(10)?;
+
+        let answer = Day10.part2(&input)?;
+
+        println!("Day 10 Part 2");
+        println!("{answer}");
+
+        Ok(())
+    }
+}
+
+/// Cathode-Ray Tube.
+pub struct Day10;
+
+impl Puzzle for Day10 {
+    type Input<'a> = CPU;
+
+    fn parse(&self, input: &str) -> Result<CPU, Error> {
+        CPU::parse(input)
+    }
+
+    fn solve_part1(&self, cpu: &CPU) -> Result<Answer, Error> {
+        let cycles = vec![20, 60, 100, 140, 180, 220];
+
+        Ok(cpu.get_sum_of_signal_strengths_at_cycles(&cycles)?.into())
+    }
+
+    fn solve_part2(&self, cpu: &CPU) -> Result<Answer, Error> {
         let crt_row_cycle_ranges = vec![
             (1, 40),
             (41, 80),
@@ -394,21 +429,15 @@ pub mod part2 {
             (201, 240),
         ];
 
-        let answer = cpu.get_crt_output(&crt_row_cycle_ranges)?;
-
-        println!("Day 10 Part 2");
-        println!("{answer}");
-
-        Ok(())
+        Ok(Answer::from_crt(
+            &cpu.get_crt_output(&crt_row_cycle_ranges)?,
+        ))
     }
 }
 
 /// Represents the CPU that theis in the elves' communication device.
 #[derive(Debug)]
 pub struct CPU {
-    /// A list of the CPU's instructiohs.
-    instructions: Vec<Instruction>,
-
     /// History of the execution of CPU instructions.
     execution_history: Vec<Registers>,
 }
@@ -418,23 +447,26 @@ impl CPU {
     pub fn parse(input: &str) -> Result<Self, Error> {
         let mut instructions = vec![];
 
-        for line in input.lines() {
+        for (line_number, line) in (1..).zip(input.lines()) {
             let line = line.trim();
             if line.is_empty() {
                 continue;
             }
 
+            let parse_error = |reason| Error::parse(10, line_number, line, reason);
+
             let instruction = match line {
                 "noop" => Instruction::Noop,
                 line if line.starts_with("addx ") => {
-                    let parts = line.split(" ").collect::<Vec<&str>>();
+                    let parts = line.split(' ').collect::<Vec<&str>>();
                     let number = parts
                         .get(1)
-                        .context("addx instruction is missing its argument")?
-                        .parse::<i32>()?;
+                        .ok_or_else(|| parse_error("addx instruction is missing its argument"))?
+                        .parse::<i32>()
+                        .map_err(|_| parse_error("invalid addx argument"))?;
                     Instruction::Addx(number)
                 }
-                _ => bail!("Invalid instruction: {line}"),
+                _ => return Err(parse_error("invalid instruction")),
             };
 
             instructions.push(instruction);
@@ -442,10 +474,7 @@ impl CPU {
 
         let execution_history = Self::execute(&instructions, Registers { x: 1 })?;
 
-        Ok(Self {
-            instructions,
-            execution_history,
-        })
+        Ok(Self { execution_history })
     }
 
     /// Calculates and returns the sum of the signal strengths at the specified cycles.
@@ -455,18 +484,24 @@ impl CPU {
         for cycle_number in cycles.iter() {
             // The -1 here is to be able to see the register value AFTER the particular cycle has
             // completed.
-            let cycle_index = usize::try_from(*cycle_number)
-                .context("Unable to cast cycle_number from u32 to usize!")?
-                - 1;
-            let Registers { x } = self.execution_history.get(cycle_index).ok_or_else(|| {
-                anyhow!(
-                    "out of bounds while getting register history at cycle number {cycle_number}"
+            let out_of_bounds = || {
+                Error::unsolvable(
+                    10,
+                    format!(
+                        "out of bounds while getting register history at cycle number {cycle_number}"
+                    ),
                 )
-            })?;
-
-            let strength = i32::try_from(*cycle_number)
-                .context("Unable to cast cycle_number from u32 to i32!")?
-                * x;
+            };
+            let cycle_index = usize::try_from(*cycle_number)
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .ok_or_else(out_of_bounds)?;
+            let Registers { x } = self
+                .execution_history
+                .get(cycle_index)
+                .ok_or_else(out_of_bounds)?;
+
+            let strength = i32::try_from(*cycle_number).map_err(|_| out_of_bounds())? * x;
             signal_strengths.push(strength);
         }
 
@@ -476,21 +511,20 @@ impl CPU {
     /// Executes instructions and returns the register history for each cycle as a list of
     /// registers.
     pub fn execute(
-        instructions: &Vec<Instruction>,
+        instructions: &[Instruction],
         initial_registers: Registers,
     ) -> Result<Vec<Registers>, Error> {
-        let mut registers = initial_registers.clone();
+        let mut registers = initial_registers;
         let mut register_history = vec![initial_registers];
 
         for instruction in instructions.iter() {
             match instruction {
                 Instruction::Noop => {
-                    registers = registers.clone();
                     register_history.push(registers);
                 }
                 Instruction::Addx(x) => {
                     // This takes two cycles, for the first cycle, nothing changes.
-                    register_history.push(registers.clone());
+                    register_history.push(registers);
 
                     // The value gets updated during the next cycle.
                     registers = Registers { x: registers.x + x };
@@ -516,14 +550,24 @@ impl CPU {
                     // Grab the sprite position.
                     // The -1 here is to be able to see the register value AFTER the particular cycle has
                     // completed.
-                    let Registers { x: sprite_position } = self
-                        .execution_history
-                        .get(cycle_number - 1)
-                        .context("Unable to get execution history at CPU cycle {cycle_number}")?;
+                    let Registers { x: sprite_position } = cycle_number
+                        .checked_sub(1)
+                        .and_then(|cycle_index| self.execution_history.get(cycle_index))
+                        .ok_or_else(|| {
+                            Error::unsolvable(
+                                10,
+                                format!(
+                                    "Unable to get execution history at CPU cycle {cycle_number}"
+                                ),
+                            )
+                        })?;
 
                     // Convert index to i32 for comparison with sprite_index.
-                    let pixel_position = i32::try_from(index).with_context(|| {
-                        format!("Unable to convert index from {index}usize to i32!")
+                    let pixel_position = i32::try_from(index).map_err(|_| {
+                        Error::unsolvable(
+                            10,
+                            format!("Unable to convert index from {index}usize to i32!"),
+                        )
                     })?;
 
                     // Set the pixel according to whether it falls within the 3-wide sprite.
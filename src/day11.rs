@@ -4,7 +4,11 @@
 
 use std::cell::RefCell;
 
-use anyhow::{bail, Context, Error};
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
 
 #[cfg(test)]
 mod tests {
@@ -92,6 +96,23 @@ Monkey 3:
 
         Ok(())
     }
+
+    #[test]
+    fn parse_reports_the_line_with_an_invalid_item() {
+        let input = "
+Monkey 0:
+  Starting items: 79, ninety-eight
+";
+
+        let Err(error) = MonkeySim::parse(input) else {
+            panic!("Expected an error");
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "day 11, line 3: invalid number \" ninety-eight\": \"Starting items: 79, ninety-eight\""
+        );
+    }
 }
 
 /// Simulates monkey business.
@@ -111,12 +132,24 @@ impl MonkeySim {
         let mut monkeys = vec![];
         let mut cell = RefCell::new(None);
 
-        for line in input.lines() {
+        for (line_number, line) in (1..).zip(input.lines()) {
             let line = line.trim();
             if line.is_empty() {
                 continue;
             }
 
+            let parse_error = |reason: &str| Error::parse(11, line_number, line, reason);
+            let parse_number = |s: &str| {
+                s.trim()
+                    .parse::<u64>()
+                    .map_err(|_| parse_error(&format!("invalid number {s:?}")))
+            };
+            let parse_monkey_number = |s: &str| {
+                s.trim()
+                    .parse::<usize>()
+                    .map_err(|_| parse_error(&format!("invalid monkey number {s:?}")))
+            };
+
             if line.starts_with("Monkey ") {
                 // We're done with the previous monkey, add it to our list.
                 if let Some(monkey) = cell.borrow_mut().take() {
@@ -128,10 +161,7 @@ impl MonkeySim {
                     let (_, end_of_line) = line.split_at("Monkey ".len());
                     let parts = end_of_line.split(':').collect::<Vec<&str>>();
 
-                    parts
-                        .first()
-                        .with_context(|| format!("Parsing monkey number from line {}", line))?
-                        .parse::<usize>()?
+                    parse_monkey_number(parts.first().copied().unwrap_or_default())?
                 };
 
                 // Reset the variables.
@@ -146,10 +176,9 @@ impl MonkeySim {
                 }));
             } else if line.starts_with("Starting items: ") {
                 let Some(ref mut monkey) = *cell.borrow_mut() else {
-                    bail!(
-                        "No monkey initialized - parsing starting items from line {}",
-                        line
-                    );
+                    return Err(parse_error(
+                        "no monkey initialized - parsing starting items",
+                    ));
                 };
 
                 // Parse items.
@@ -158,21 +187,14 @@ impl MonkeySim {
                     let items_str = end_of_line.split(',').collect::<Vec<&str>>();
                     items_str
                         .iter()
-                        .map(|item_str| {
-                            item_str.trim().parse::<u64>().with_context(|| {
-                                format!("Parsing starting items from line {}: '{}'", line, item_str)
-                            })
-                        })
+                        .map(|item_str| parse_number(item_str))
                         .collect::<Result<Vec<u64>, Error>>()?
                 };
 
                 monkey.items = RefCell::new(items);
             } else if line.starts_with("Operation: new = ") {
                 let Some(ref mut monkey) = *cell.borrow_mut() else {
-                    bail!(
-                        "No monkey initialized - parsing operation from line {}",
-                        line
-                    );
+                    return Err(parse_error("no monkey initialized - parsing operation"));
                 };
 
                 let operation = {
@@ -183,15 +205,10 @@ impl MonkeySim {
                         let get_operand = |input: Option<&str>| {
                             Ok(match input {
                                 None => {
-                                    bail!(
-                                        "No operands found in the operation in the line: {}",
-                                        line
-                                    )
-                                }
-                                Some(s) if s == "old" => Operand::OldValue,
-                                Some(number_str) => {
-                                    Operand::Constant(number_str.trim().parse::<u64>()?)
+                                    return Err(parse_error("no operands found in the operation"))
                                 }
+                                Some("old") => Operand::OldValue,
+                                Some(number_str) => Operand::Constant(parse_number(number_str)?),
                             })
                         };
 
@@ -202,48 +219,42 @@ impl MonkeySim {
                     };
 
                     match parts.get(1) {
-                        None => bail!("No operator found in the operation in the line: {}.", line),
+                        None => return Err(parse_error("no operator found in the operation")),
                         Some(s) if s == &"*" => Operation::Product(a, b),
                         Some(s) if s == &"+" => Operation::Addition(a, b),
-                        _ => bail!("Unsupported operation gound on the line: {}.", line),
+                        _ => return Err(parse_error("unsupported operation")),
                     }
                 };
 
                 monkey.operation = Some(operation);
             } else if line.starts_with("Test: divisible by ") {
                 let Some(ref mut monkey) = *cell.borrow_mut() else {
-                    bail!(
-                        "No monkey initialized - parsing test operand from line {}",
-                        line
-                    );
+                    return Err(parse_error("no monkey initialized - parsing test operand"));
                 };
 
                 let (_, end_of_line) = line.split_at("Test: divisible by ".len());
-                let test = end_of_line.trim().parse::<u64>()?;
+                let test = parse_number(end_of_line)?;
+                if test == 0 {
+                    return Err(parse_error("test can not be divisible by zero"));
+                }
 
                 monkey.test = test;
             } else if line.starts_with("If true: throw to monkey ") {
                 let Some(ref mut monkey) = *cell.borrow_mut() else {
-                    bail!(
-                        "No monkey initialized - parsing test operand from line {}",
-                        line
-                    );
+                    return Err(parse_error("no monkey initialized - parsing test operand"));
                 };
 
                 let (_, end_of_line) = line.split_at("If true: throw to monkey ".len());
-                let target_monkey_number = end_of_line.trim().parse::<usize>()?;
+                let target_monkey_number = parse_monkey_number(end_of_line)?;
 
                 monkey.test_pass_monkey_number = target_monkey_number;
             } else if line.starts_with("If false: throw to monkey ") {
                 let Some(ref mut monkey) = *cell.borrow_mut() else {
-                    bail!(
-                        "No monkey initialized - parsing test operand from line {}",
-                        line
-                    );
+                    return Err(parse_error("no monkey initialized - parsing test operand"));
                 };
 
                 let (_, end_of_line) = line.split_at("If false: throw to monkey ".len());
-                let target_monkey_number = end_of_line.trim().parse::<usize>()?;
+                let target_monkey_number = parse_monkey_number(end_of_line)?;
 
                 monkey.test_fail_monkey_number = target_monkey_number;
             }
@@ -302,22 +313,37 @@ impl MonkeySim {
                 for monkey in round.borrow().iter() {
                     // Grab the number of inspections about to happen (because the list will get
                     // drained.
-                    let num_inspections = u64::try_from(monkey.borrow().items.borrow().len())?;
+                    let num_inspections = monkey.borrow().items.borrow().len() as u64;
 
                     // Inspect each item.
                     for item in monkey.borrow().items.borrow_mut().drain(..) {
                         let monkey = monkey.borrow();
 
                         let Some(ref operation) = monkey.operation else {
-                            bail!("No operation found for monkey #{}, round #{}.", monkey.number, round_number);
+                            return Err(Error::unsolvable(
+                                11,
+                                format!(
+                                    "No operation found for monkey #{}, round #{}.",
+                                    monkey.number, round_number
+                                ),
+                            ));
                         };
+                        if monkey.test == 0 {
+                            return Err(Error::unsolvable(
+                                11,
+                                format!("No test found for monkey #{}.", monkey.number),
+                            ));
+                        }
                         let mut worry_level = operation.execute(item);
 
                         worry_level = match worry_manager {
                             WorryManager::Constant(n) => (worry_level as f64 / n).floor() as u64,
                             WorryManager::Dynamic => {
                                 let Some(divisor) = product_of_primes else {
-                                    bail!("No product of primes when worry manager is dynamic!");
+                                    return Err(Error::unsolvable(
+                                        11,
+                                        "No product of primes when worry manager is dynamic!",
+                                    ));
                                 };
                                 worry_level % divisor
                             }
@@ -334,10 +360,13 @@ impl MonkeySim {
                         round
                             .borrow()
                             .get(recipient_monkey_number)
-                            .with_context(|| {
-                                format!(
-                                    "Trying to get recipient monkey #{} to throw item to.",
-                                    recipient_monkey_number
+                            .ok_or_else(|| {
+                                Error::unsolvable(
+                                    11,
+                                    format!(
+                                        "Trying to get recipient monkey #{} to throw item to.",
+                                        recipient_monkey_number
+                                    ),
                                 )
                             })?
                             .borrow_mut()
@@ -364,7 +393,7 @@ impl MonkeySim {
             num_monkey_inspections
         };
 
-        num_monkey_inspections.sort_by(|a, b| (b.1).cmp(&a.1));
+        num_monkey_inspections.sort_by_key(|m| std::cmp::Reverse(m.1));
         let top_monkeys = num_monkey_inspections.iter().take(top_n);
         let monkey_business_level = top_monkeys.fold(1u64, |product, tuple| product * tuple.1);
 
@@ -460,20 +489,38 @@ pub enum WorryManager {
     Dynamic,
 }
 
+/// Monkey in the Middle.
+pub struct Day11;
+
+impl Puzzle for Day11 {
+    type Input<'a> = MonkeySim;
+
+    fn parse(&self, input: &str) -> Result<MonkeySim, Error> {
+        MonkeySim::parse(input)
+    }
+
+    fn solve_part1(&self, monkey_sim: &MonkeySim) -> Result<Answer, Error> {
+        let answer = monkey_sim.get_monkey_business_level(20, 2, WorryManager::Constant(3.0))?;
+
+        Ok(answer.into())
+    }
+
+    fn solve_part2(&self, monkey_sim: &MonkeySim) -> Result<Answer, Error> {
+        let answer = monkey_sim.get_monkey_business_level(10_000, 2, WorryManager::Dynamic)?;
+
+        Ok(answer.into())
+    }
+}
+
 /// Part 1
 pub mod part1 {
-    use std::{env::current_dir, fs::read_to_string};
-
     use super::*;
 
     /// The solution for Day 11 Part 1
     pub fn solution() -> Result<(), Error> {
-        let filename = current_dir()?.join("src/data/day11.txt");
-        let input = read_to_string(filename)?;
-
-        let monkey_sim = MonkeySim::parse(&input)?;
+        let input = InputSource::default().read(11)?;
 
-        let answer = monkey_sim.get_monkey_business_level(20, 2, WorryManager::Constant(3.0))?;
+        let answer = Day11.part1(&input)?;
 
         println!("Day 11 Solution Part 1 = {}", answer);
 
@@ -483,18 +530,13 @@ pub mod part1 {
 
 /// Part 2
 pub mod part2 {
-    use std::{env::current_dir, fs::read_to_string};
-
     use super::*;
 
     /// The solution for Day 11 Part 1
     pub fn solution() -> Result<(), Error> {
-        let filename = current_dir()?.join("src/data/day11.txt");
-        let input = read_to_string(filename)?;
+        let input = InputSource::default().read(11)?;
 
-        let monkey_sim = MonkeySim::parse(&input)?;
-
-        let answer = monkey_sim.get_monkey_business_level(10_000, 2, WorryManager::Dynamic)?;
+        let answer = Day11.part2(&input)?;
 
         println!("Day 11 Solution Part 2 = {}", answer);
 
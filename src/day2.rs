@@ -1,19 +1,46 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn calculated_rock_paper_scissors_tournament_score_with_my_strategy_returns_the_correct_score() {
+    fn calculated_rock_paper_scissors_tournament_score_with_my_strategy_returns_the_correct_score()
+    {
         let input = "A Y
 B X
 C Z
 
 ";
-        let total_score = calculated_rock_paper_scissors_tournament_score_with_my_strategy(input);
+        let total_score =
+            calculated_rock_paper_scissors_tournament_score_with_my_strategy(input).unwrap();
 
         assert_eq!(total_score, 15);
     }
 
+    #[test]
+    fn calculated_rock_paper_scissors_tournament_score_reports_invalid_tokens() {
+        let input = "A Y
+B W
+";
+        let error =
+            calculated_rock_paper_scissors_tournament_score_with_my_strategy(input).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 2: invalid shape token: \"B W\""
+        );
+    }
+
     #[test]
     fn calculated_rock_paper_scissors_tournament_score_with_elf_strategy_returns_the_correct_score()
     {
@@ -22,191 +49,1105 @@ B X
 C Z
 
 ";
-        let total_score = calculated_rock_paper_scissors_tournament_score_with_elf_strategy(input);
+        let total_score =
+            calculated_rock_paper_scissors_tournament_score_with_elf_strategy(input).unwrap();
 
         assert_eq!(total_score, 12);
     }
+
+    #[test]
+    fn tournament_keeps_every_round() -> Result<(), Error> {
+        let input = "A Y
+B X
+
+C Z
+";
+        let tournament = Tournament::parse(input, Strategy::Elf)?;
+
+        let outcomes = tournament
+            .rounds()
+            .iter()
+            .map(|round| {
+                (
+                    round.line_number,
+                    round.my_shape,
+                    round.outcome,
+                    round.score,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outcomes,
+            vec![
+                (1, Shape::ROCK, Goal::Draw, 4),
+                (2, Shape::ROCK, Goal::Lose, 1),
+                (4, Shape::ROCK, Goal::Win, 7),
+            ]
+        );
+        assert_eq!(tournament.running_scores(), vec![4, 5, 12]);
+        assert_eq!(
+            tournament.record(),
+            Record {
+                wins: 1,
+                draws: 1,
+                losses: 1
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock_beats_two_shapes_each() {
+        let ruleset = Ruleset::rock_paper_scissors_lizard_spock();
+        let beats = |shape| {
+            ruleset
+                .shapes()
+                .filter(|&other| ruleset.outcome(shape, other) == Goal::Win)
+                .collect::<Vec<Shape>>()
+        };
+
+        assert_eq!(beats(Shape::ROCK), vec![Shape::SCISSORS, Shape::LIZARD]);
+        assert_eq!(beats(Shape::PAPER), vec![Shape::ROCK, Shape::SPOCK]);
+        assert_eq!(beats(Shape::SCISSORS), vec![Shape::PAPER, Shape::LIZARD]);
+        assert_eq!(beats(Shape::SPOCK), vec![Shape::ROCK, Shape::SCISSORS]);
+        assert_eq!(beats(Shape::LIZARD), vec![Shape::PAPER, Shape::SPOCK]);
+    }
+
+    #[test]
+    fn ruleset_new_rejects_games_without_exactly_one_winner() {
+        assert_eq!(
+            Ruleset::new(&["Rock", "Paper"], &[1]),
+            Err(RulesetError::TooFewShapes(2))
+        );
+        assert_eq!(
+            Ruleset::new(&["A", "B", "C"], &[1, 2]),
+            Err(RulesetError::AmbiguousOffset(1))
+        );
+        assert_eq!(
+            Ruleset::new(&["A", "B", "C"], &[3]),
+            Err(RulesetError::InvalidOffset {
+                offset: 3,
+                num_shapes: 3
+            })
+        );
+        assert_eq!(
+            Ruleset::balanced(&["A", "B", "C", "D"]),
+            Err(RulesetError::AmbiguousOffset(1))
+        );
+        assert!(Ruleset::new(&["A", "B", "C", "D", "E"], &[1, 2]).is_ok());
+    }
+
+    #[test]
+    fn games_report_typed_errors() {
+        assert_eq!(
+            Ruleset::rock_paper_scissors().with_shape_points(&[1, 2]),
+            Err(RulesetError::ShapePointsMismatch {
+                num_shapes: 3,
+                num_points: 2
+            })
+        );
+
+        let tokens = TokenMapping::default().my_shape("s", Shape::SPOCK);
+        let Err(error) = Game::new(Ruleset::rock_paper_scissors(), tokens) else {
+            panic!("Expected an error");
+        };
+        assert_eq!(error, RulesetError::UnknownShape(Shape::SPOCK));
+        assert_eq!(
+            error.to_string(),
+            "a token maps to shape 3, which isn't in the ruleset"
+        );
+    }
+
+    #[test]
+    fn tournament_parse_with_plays_a_custom_game() -> Result<(), Error> {
+        let ruleset = Ruleset::rock_paper_scissors_lizard_spock()
+            .with_shape_points(&[1, 1, 1, 2, 2])
+            .unwrap()
+            .with_outcome_points(0, 1, 3);
+        let tokens = TokenMapping::default()
+            .their_shape("r", Shape::ROCK)
+            .their_shape("k", Shape::SPOCK)
+            .my_shape("l", Shape::LIZARD)
+            .my_goal("w", Goal::Win);
+        let game = Game::new(ruleset, tokens).unwrap();
+
+        let mine = Tournament::parse_with("r l\nk l\n", Strategy::Mine, &game)?;
+        let elf = Tournament::parse_with("k w\n", Strategy::Elf, &game)?;
+
+        assert_eq!(mine.running_scores(), vec![2, 7]);
+        assert_eq!(elf.rounds()[0].my_shape, Shape::LIZARD);
+        assert_eq!(elf.total_score(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn decoder_scores_every_interpretation() -> Result<(), Error> {
+        let decoder = Decoder::decode("A Y\nB X\nC Z\n")?;
+
+        let scores = decoder
+            .decodings()
+            .iter()
+            .map(|decoding| (decoding.interpretation.to_string(), decoding.total_score))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            scores,
+            vec![
+                ("X=Rock, Y=Paper, Z=Scissors".to_string(), 15),
+                ("X=Rock, Y=Scissors, Z=Paper".to_string(), 6),
+                ("X=Paper, Y=Rock, Z=Scissors".to_string(), 15),
+                ("X=Paper, Y=Scissors, Z=Rock".to_string(), 15),
+                ("X=Scissors, Y=Rock, Z=Paper".to_string(), 15),
+                ("X=Scissors, Y=Paper, Z=Rock".to_string(), 24),
+                ("X=Lose, Y=Draw, Z=Win".to_string(), 12),
+            ]
+        );
+        assert_eq!(decoder.consistent_with(15).count(), 4);
+        assert_eq!(
+            decoder.consistent_with(12).collect::<Vec<_>>(),
+            vec![Interpretation::Goals]
+        );
+        assert_eq!(decoder.consistent_with(100).count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn best_response_wins_every_round_without_constraints() -> Result<(), Error> {
+        let tournament =
+            Tournament::best_response("A Y\nB X\nC Z\n", &Game::puzzle(), Constraints::default())?;
+
+        let my_shapes = tournament
+            .rounds()
+            .iter()
+            .map(|round| round.my_shape)
+            .collect::<Vec<Shape>>();
+        assert_eq!(my_shapes, vec![Shape::PAPER, Shape::SCISSORS, Shape::ROCK]);
+        assert_eq!(tournament.total_score(), 8 + 9 + 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn best_response_meets_the_constraints() -> Result<(), Error> {
+        let input = "A\nA\nA\nA\n";
+
+        let repeats = Tournament::best_response(
+            input,
+            &Game::puzzle(),
+            Constraints::default().repeating_at_most(2),
+        )?;
+        let losses = Tournament::best_response(
+            input,
+            &Game::puzzle(),
+            Constraints::default().losing_exactly(1),
+        )?;
+
+        // Paper wins for 8 points, so one round in three has to draw (4) or lose (3) instead.
+        assert_eq!(repeats.total_score(), 8 + 8 + 4 + 8);
+        assert_eq!(losses.total_score(), 8 + 8 + 8 + 3);
+        assert_eq!(losses.record().losses, 1);
+
+        let Err(error) = Tournament::best_response(
+            "A\n",
+            &Game::puzzle(),
+            Constraints::default().losing_exactly(2),
+        ) else {
+            panic!("Expected an error");
+        };
+        assert_eq!(error.to_string(), "day 2: no moves meet the constraints");
+
+        Ok(())
+    }
+
+    #[test]
+    fn tournament_displays_a_table_of_rounds() -> Result<(), Error> {
+        let tournament = Tournament::parse("A Y\nB X\nC Z\n", Strategy::Mine)?;
+
+        assert_eq!(
+            tournament.to_string(),
+            " Line  Them      Me        Outcome  Score  Total
+    1  Rock      Paper     Win          8      8
+    2  Paper     Rock      Lose         1      9
+    3  Scissors  Scissors  Draw         6     15
+Wins: 1, draws: 1, losses: 1, total score: 15"
+        );
+
+        Ok(())
+    }
 }
 
 pub mod part1 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day2.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(2)?;
 
-        let total_score = calculated_rock_paper_scissors_tournament_score_with_my_strategy(&input);
+        let total_score = Day2.part1(&input)?;
 
         println!("Total Score = {}", total_score);
+
+        Ok(())
     }
 }
 
 pub mod part2 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day2.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(2)?;
 
-        let total_score = calculated_rock_paper_scissors_tournament_score_with_elf_strategy(&input);
+        let total_score = Day2.part2(&input)?;
 
         println!("Total Score = {}", total_score);
+
+        Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
-enum Shape {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+/// Rock Paper Scissors.
+pub struct Day2;
+
+impl Puzzle for Day2 {
+    type Input<'a> = Vec<GuideLine<'a>>;
+
+    /// The tokens are only resolved to shapes and goals by each part, since the parts read the
+    /// second column differently.
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<GuideLine<'a>>, Error> {
+        parse_guide(input)
+    }
+
+    fn solve_part1(&self, guide: &Self::Input<'_>) -> Result<Answer, Error> {
+        let tournament = Tournament::play(guide, Strategy::Mine, &Game::puzzle())?;
+
+        Ok(tournament.total_score().into())
+    }
+
+    fn solve_part2(&self, guide: &Self::Input<'_>) -> Result<Answer, Error> {
+        let tournament = Tournament::play(guide, Strategy::Elf, &Game::puzzle())?;
+
+        Ok(tournament.total_score().into())
+    }
 }
 
-#[derive(Debug)]
-enum Goal {
+/// A shape, identified by its position in the game's cycle of shapes.
+///
+/// The constants are the shapes of Rock Paper Scissors Lizard Spock, in the cycle order used by
+/// [`Ruleset::rock_paper_scissors_lizard_spock`]. The first three are Rock Paper Scissors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Shape(pub usize);
+
+impl Shape {
+    pub const ROCK: Shape = Shape(0);
+    pub const PAPER: Shape = Shape(1);
+    pub const SCISSORS: Shape = Shape(2);
+    pub const SPOCK: Shape = Shape(3);
+    pub const LIZARD: Shape = Shape(4);
+}
+
+/// The outcome of a round for me, which is also what the elf's strategy asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Goal {
     Lose,
     Draw,
     Win,
 }
 
-impl TryFrom<&str> for Goal {
-    type Error = &'static str;
+/// How to read the second column of the strategy guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// My guess: the second column is the shape to play.
+    Mine,
+
+    /// What the elf meant: the second column is how the round needs to end.
+    Elf,
+}
+
+/// Why a game could not be set up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum RulesetError {
+    /// A game needs at least three shapes for every shape to beat another.
+    #[error("a game needs at least three shapes, but has {0}")]
+    TooFewShapes(usize),
+
+    /// A winning offset isn't the distance between two different shapes.
+    #[error("winning offset {offset} must be between 1 and {}", .num_shapes - 1)]
+    InvalidOffset { offset: usize, num_shapes: usize },
+
+    /// The shapes at this offset from each other either both beat each other or neither does.
+    #[error("shapes {0} apart in the cycle need exactly one winner")]
+    AmbiguousOffset(usize),
+
+    /// There isn't exactly one point value for each shape.
+    #[error("expected {num_shapes} shape point values, found {num_points}")]
+    ShapePointsMismatch {
+        num_shapes: usize,
+        num_points: usize,
+    },
+
+    /// A token maps to a shape that isn't in the ruleset.
+    #[error("a token maps to shape {}, which isn't in the ruleset", .0.0)]
+    UnknownShape(Shape),
+}
+
+/// The rules of a cyclic game like Rock Paper Scissors, and how it is scored.
+///
+/// The shapes form a cycle, and whether one shape beats another only depends on how far apart
+/// they are in it: my shape beats theirs when `(mine - theirs) mod n` is one of the winning
+/// offsets. For every pair of different shapes exactly one beats the other, so each offset `d`
+/// wins exactly when `n - d` loses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    shape_names: Vec<String>,
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "X" => Ok(Self::Lose),
-            "Y" => Ok(Self::Draw),
-            "Z" => Ok(Self::Win),
-            _ => Err("Invalid Goal Token"),
+    /// Whether my shape wins at each offset `(mine - theirs) mod n`.
+    is_winning_offset: Vec<bool>,
+
+    /// The points for playing each shape.
+    shape_points: Vec<u32>,
+
+    /// The points for losing, drawing and winning a round.
+    outcome_points: [u32; 3],
+}
+
+impl Ruleset {
+    /// Creates a ruleset where each shape beats the shapes at the given offsets before it in the
+    /// cycle.
+    ///
+    /// Playing a shape scores its position in the cycle (starting at 1), and a round scores 0 for
+    /// a loss, 3 for a draw and 6 for a win, as in the puzzle.
+    pub fn new(shape_names: &[&str], winning_offsets: &[usize]) -> Result<Self, RulesetError> {
+        let num_shapes = shape_names.len();
+        if num_shapes < 3 {
+            return Err(RulesetError::TooFewShapes(num_shapes));
         }
+
+        let mut is_winning_offset = vec![false; num_shapes];
+        for &offset in winning_offsets {
+            let is_winning = is_winning_offset
+                .get_mut(offset)
+                .filter(|_| offset != 0)
+                .ok_or(RulesetError::InvalidOffset { offset, num_shapes })?;
+            *is_winning = true;
+        }
+
+        if let Some(d) =
+            (1..num_shapes).find(|&d| is_winning_offset[d] == is_winning_offset[num_shapes - d])
+        {
+            return Err(RulesetError::AmbiguousOffset(d));
+        }
+
+        Ok(Ruleset {
+            shape_names: shape_names.iter().map(|name| name.to_string()).collect(),
+            is_winning_offset,
+            shape_points: (1..=num_shapes as u32).collect(),
+            outcome_points: [0, 3, 6],
+        })
+    }
+
+    /// Creates a ruleset where each shape beats every other shape an odd number of places before
+    /// it in the cycle, so that every shape beats half of the others. Needs an odd number of
+    /// shapes.
+    pub fn balanced(shape_names: &[&str]) -> Result<Self, RulesetError> {
+        let winning_offsets = (1..shape_names.len()).step_by(2).collect::<Vec<usize>>();
+
+        Self::new(shape_names, &winning_offsets)
+    }
+
+    /// Rock Paper Scissors, scored as in the puzzle.
+    pub fn rock_paper_scissors() -> Self {
+        Self::balanced(&["Rock", "Paper", "Scissors"])
+            .expect("Rock Paper Scissors is a balanced game")
+    }
+
+    /// Rock Paper Scissors Lizard Spock, where every shape beats two others.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::balanced(&["Rock", "Paper", "Scissors", "Spock", "Lizard"])
+            .expect("Rock Paper Scissors Lizard Spock is a balanced game")
+    }
+
+    /// Replaces the points for playing each shape, in cycle order.
+    pub fn with_shape_points(mut self, shape_points: &[u32]) -> Result<Self, RulesetError> {
+        if shape_points.len() != self.num_shapes() {
+            return Err(RulesetError::ShapePointsMismatch {
+                num_shapes: self.num_shapes(),
+                num_points: shape_points.len(),
+            });
+        }
+
+        self.shape_points = shape_points.to_vec();
+        Ok(self)
+    }
+
+    /// Replaces the points for losing, drawing and winning a round.
+    pub fn with_outcome_points(mut self, lose: u32, draw: u32, win: u32) -> Self {
+        self.outcome_points = [lose, draw, win];
+        self
+    }
+
+    pub fn num_shapes(&self) -> usize {
+        self.shape_names.len()
+    }
+
+    /// Every shape in the game, in cycle order.
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.num_shapes()).map(Shape)
+    }
+
+    pub fn shape_name(&self, shape: Shape) -> Option<&str> {
+        self.shape_names.get(shape.0).map(String::as_str)
+    }
+
+    /// How a round ends for me.
+    pub fn outcome(&self, mine: Shape, theirs: Shape) -> Goal {
+        match self.offset(mine, theirs) {
+            0 => Goal::Draw,
+            offset if self.is_winning_offset[offset] => Goal::Win,
+            _ => Goal::Lose,
+        }
+    }
+
+    /// The points for playing a shape.
+    pub fn point_value(&self, shape: Shape) -> u32 {
+        self.shape_points.get(shape.0).copied().unwrap_or(0)
+    }
+
+    /// My score for a round: the points for my shape plus the points for the outcome.
+    pub fn score(&self, mine: Shape, theirs: Shape) -> u32 {
+        let outcome_points = match self.outcome(mine, theirs) {
+            Goal::Lose => self.outcome_points[0],
+            Goal::Draw => self.outcome_points[1],
+            Goal::Win => self.outcome_points[2],
+        };
+
+        self.point_value(mine) + outcome_points
+    }
+
+    /// The shape that ends a round against `theirs` as I want it to. When several shapes would
+    /// do, the one nearest after `theirs` in the cycle is chosen.
+    pub fn meets_goal(&self, goal: Goal, theirs: Shape) -> Shape {
+        self.shapes()
+            .map(|offset| Shape((theirs.0 + offset.0) % self.num_shapes()))
+            .find(|&mine| self.outcome(mine, theirs) == goal)
+            .unwrap_or(theirs)
+    }
+
+    /// How far `mine` is after `theirs` in the cycle.
+    fn offset(&self, mine: Shape, theirs: Shape) -> usize {
+        let num_shapes = self.num_shapes();
+
+        (mine.0 % num_shapes + num_shapes - theirs.0 % num_shapes) % num_shapes
     }
 }
 
-#[derive(Debug)]
-struct Round {
-    _my_shape_my_strategy: Shape,
-    _my_shape_elf_strategy: Shape,
-    _their_shape: Shape,
-    my_score_my_strategy: u32,
-    my_score_elf_strategy: u32,
+/// How the tokens in a strategy guide map to shapes and goals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenMapping {
+    their_shapes: HashMap<String, Shape>,
+    my_shapes: HashMap<String, Shape>,
+    my_goals: HashMap<String, Goal>,
 }
 
-impl TryFrom<&str> for Round {
-    type Error = &'static str;
+impl TokenMapping {
+    /// Maps a token in the first column to their shape.
+    pub fn their_shape(mut self, token: &str, shape: Shape) -> Self {
+        self.their_shapes.insert(token.to_string(), shape);
+        self
+    }
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut parts: Vec<&str> = value.split(' ').collect();
+    /// Maps a token in the second column to my shape, for [`Strategy::Mine`].
+    pub fn my_shape(mut self, token: &str, shape: Shape) -> Self {
+        self.my_shapes.insert(token.to_string(), shape);
+        self
+    }
 
-        let my_token = parts.pop().unwrap();
-        let their_token = parts.pop().unwrap();
+    /// Maps a token in the second column to my goal, for [`Strategy::Elf`].
+    pub fn my_goal(mut self, token: &str, goal: Goal) -> Self {
+        self.my_goals.insert(token.to_string(), goal);
+        self
+    }
 
-        let my_shape_my_strategy = Shape::try_from(my_token)?;
-        let their_shape = Shape::try_from(their_token)?;
+    /// The puzzle's tokens: A, B and C for their shape; X, Y and Z for my shape or my goal.
+    pub fn puzzle() -> Self {
+        Self::default()
+            .their_shape("A", Shape::ROCK)
+            .their_shape("B", Shape::PAPER)
+            .their_shape("C", Shape::SCISSORS)
+            .my_shape("X", Shape::ROCK)
+            .my_shape("Y", Shape::PAPER)
+            .my_shape("Z", Shape::SCISSORS)
+            .my_goal("X", Goal::Lose)
+            .my_goal("Y", Goal::Draw)
+            .my_goal("Z", Goal::Win)
+    }
 
-        let my_goal = Goal::try_from(my_token)?;
-        let my_shape_elf_strategy = Shape::meets_goal(&my_goal, &their_shape);
+    fn parse_their_shape(&self, token: &str) -> Result<Shape, &'static str> {
+        self.their_shapes
+            .get(token)
+            .copied()
+            .ok_or("invalid shape token")
+    }
 
-        let my_score_my_strategy =
-            my_shape_my_strategy.point_value() + my_shape_my_strategy.score_against(&their_shape);
-        let my_score_elf_strategy =
-            my_shape_elf_strategy.point_value() + my_shape_elf_strategy.score_against(&their_shape);
+    fn parse_my_shape(&self, token: &str) -> Result<Shape, &'static str> {
+        self.my_shapes
+            .get(token)
+            .copied()
+            .ok_or("invalid shape token")
+    }
 
-        Ok(Round {
-            _my_shape_my_strategy: my_shape_my_strategy,
-            _my_shape_elf_strategy: my_shape_elf_strategy,
-            _their_shape: their_shape,
-            my_score_my_strategy,
-            my_score_elf_strategy,
-        })
+    fn parse_my_goal(&self, token: &str) -> Result<Goal, &'static str> {
+        self.my_goals
+            .get(token)
+            .copied()
+            .ok_or("invalid goal token")
     }
 }
 
-impl TryFrom<&str> for Shape {
-    type Error = &'static str;
+/// A ruleset along with the tokens used to write strategy guides for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    ruleset: Ruleset,
+    tokens: TokenMapping,
+}
+
+impl Game {
+    /// Creates a game, checking that every token maps to one of the ruleset's shapes.
+    pub fn new(ruleset: Ruleset, tokens: TokenMapping) -> Result<Self, RulesetError> {
+        let mut shapes = tokens
+            .their_shapes
+            .values()
+            .chain(tokens.my_shapes.values());
+        if let Some(&shape) = shapes.find(|shape| shape.0 >= ruleset.num_shapes()) {
+            return Err(RulesetError::UnknownShape(shape));
+        }
+
+        Ok(Game { ruleset, tokens })
+    }
 
-    fn try_from(token: &str) -> Result<Self, Self::Error> {
-        match token {
-            "A" | "X" => Ok(Shape::Rock),
-            "B" | "Y" => Ok(Shape::Paper),
-            "C" | "Z" => Ok(Shape::Scissors),
-            _ => Err("Invalid Shape Token"),
+    /// Rock Paper Scissors with the puzzle's scoring and tokens.
+    pub fn puzzle() -> Self {
+        Game {
+            ruleset: Ruleset::rock_paper_scissors(),
+            tokens: TokenMapping::puzzle(),
         }
     }
+
+    pub fn ruleset(&self) -> &Ruleset {
+        &self.ruleset
+    }
 }
 
-impl Shape {
-    fn score_against(&self, opponent: &Self) -> u32 {
-        match self {
-            Shape::Rock => match opponent {
-                Shape::Rock => 3,
-                Shape::Paper => 0,
-                Shape::Scissors => 6,
-            },
-            Shape::Paper => match opponent {
-                Shape::Rock => 6,
-                Shape::Paper => 3,
-                Shape::Scissors => 0,
-            },
-            Shape::Scissors => match opponent {
-                Shape::Rock => 0,
-                Shape::Paper => 6,
-                Shape::Scissors => 3,
+/// A line of the strategy guide, split into its two tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuideLine<'a> {
+    pub line_number: usize,
+    pub line: &'a str,
+    /// The token for the shape my opponent plays.
+    pub their_token: &'a str,
+    /// The token for my shape or goal, depending on the strategy.
+    pub my_token: &'a str,
+}
+
+/// Splits every line of a strategy guide into its two tokens, skipping empty lines.
+pub fn parse_guide(input: &str) -> Result<Vec<GuideLine<'_>>, Error> {
+    (1..)
+        .zip(input.lines())
+        .filter(|(_, line)| !line.is_empty())
+        .map(
+            |(line_number, line)| match line.split(' ').collect::<Vec<&str>>()[..] {
+                [their_token, my_token] => Ok(GuideLine {
+                    line_number,
+                    line,
+                    their_token,
+                    my_token,
+                }),
+                _ => Err(Error::parse(
+                    2,
+                    line_number,
+                    line,
+                    "expected two tokens separated by a space",
+                )),
             },
-        }
+        )
+        .collect()
+}
+
+/// A round of the tournament, as played following the strategy guide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    /// The line of the strategy guide the round came from.
+    pub line_number: usize,
+    pub their_shape: Shape,
+    pub my_shape: Shape,
+    /// How the round ended for me.
+    pub outcome: Goal,
+    /// My score for the round.
+    pub score: u32,
+}
+
+impl Round {
+    /// Plays a line of the strategy guide.
+    fn play(guide_line: &GuideLine<'_>, strategy: Strategy, game: &Game) -> Result<Self, Error> {
+        let GuideLine {
+            line_number,
+            line,
+            their_token,
+            my_token,
+        } = *guide_line;
+        let parse_error = |reason| Error::parse(2, line_number, line, reason);
+
+        let their_shape = game
+            .tokens
+            .parse_their_shape(their_token)
+            .map_err(parse_error)?;
+        let my_shape = match strategy {
+            Strategy::Mine => game.tokens.parse_my_shape(my_token).map_err(parse_error)?,
+            Strategy::Elf => {
+                let my_goal = game.tokens.parse_my_goal(my_token).map_err(parse_error)?;
+                game.ruleset.meets_goal(my_goal, their_shape)
+            }
+        };
+
+        Ok(Round {
+            line_number,
+            their_shape,
+            my_shape,
+            outcome: game.ruleset.outcome(my_shape, their_shape),
+            score: game.ruleset.score(my_shape, their_shape),
+        })
     }
+}
+
+/// A whole tournament played by following a strategy guide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    rounds: Vec<Round>,
+    ruleset: Ruleset,
+}
+
+/// The number of rounds I won, drew and lost.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Tournament {
+    /// Plays every round of the puzzle's strategy guide, skipping empty lines.
+    pub fn parse(input: &str, strategy: Strategy) -> Result<Self, Error> {
+        Self::parse_with(input, strategy, &Game::puzzle())
+    }
+
+    /// Plays every round of a strategy guide for any game, skipping empty lines.
+    pub fn parse_with(input: &str, strategy: Strategy, game: &Game) -> Result<Self, Error> {
+        Self::play(&parse_guide(input)?, strategy, game)
+    }
+
+    /// Plays every round of an already split strategy guide.
+    pub fn play(guide: &[GuideLine<'_>], strategy: Strategy, game: &Game) -> Result<Self, Error> {
+        let rounds = guide
+            .iter()
+            .map(|guide_line| Round::play(guide_line, strategy, game))
+            .collect::<Result<Vec<Round>, Error>>()?;
+
+        Ok(Tournament {
+            rounds,
+            ruleset: game.ruleset.clone(),
+        })
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    pub fn total_score(&self) -> u32 {
+        self.rounds.iter().map(|round| round.score).sum()
+    }
+
+    /// My total score after each round.
+    pub fn running_scores(&self) -> Vec<u32> {
+        self.rounds
+            .iter()
+            .scan(0, |total_score, round| {
+                *total_score += round.score;
+                Some(*total_score)
+            })
+            .collect()
+    }
+
+    pub fn record(&self) -> Record {
+        self.rounds
+            .iter()
+            .fold(Record::default(), |mut record, round| {
+                match round.outcome {
+                    Goal::Win => record.wins += 1,
+                    Goal::Draw => record.draws += 1,
+                    Goal::Lose => record.losses += 1,
+                }
+                record
+            })
+    }
+}
+
+impl Display for Tournament {
+    /// Prints a table with a row for each round, followed by the record.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>5}  {:<8}  {:<8}  {:<7}  {:>5}  {:>5}",
+            "Line", "Them", "Me", "Outcome", "Score", "Total"
+        )?;
+
+        let shape_name = |shape| self.ruleset.shape_name(shape).unwrap_or("?");
 
-    fn point_value(&self) -> u32 {
-        match self {
-            Shape::Rock => 1,
-            Shape::Paper => 2,
-            Shape::Scissors => 3,
+        for (round, total_score) in self.rounds.iter().zip(self.running_scores()) {
+            writeln!(
+                f,
+                "{:>5}  {:<8}  {:<8}  {:<7}  {:>5}  {:>5}",
+                round.line_number,
+                shape_name(round.their_shape),
+                shape_name(round.my_shape),
+                format!("{:?}", round.outcome),
+                round.score,
+                total_score
+            )?;
         }
+
+        let record = self.record();
+        write!(
+            f,
+            "Wins: {}, draws: {}, losses: {}, total score: {}",
+            record.wins,
+            record.draws,
+            record.losses,
+            self.total_score()
+        )
     }
+}
 
-    fn meets_goal(goal: &Goal, opponent: &Self) -> Self {
-        match goal {
-            Goal::Lose => match opponent {
-                Shape::Rock => Shape::Scissors,
-                Shape::Paper => Shape::Rock,
-                Shape::Scissors => Shape::Paper,
-            },
-            Goal::Draw => opponent.clone(),
-            Goal::Win => match opponent {
-                Shape::Rock => Shape::Paper,
-                Shape::Paper => Shape::Scissors,
-                Shape::Scissors => Shape::Rock,
+/// The tokens in the second column of the puzzle's strategy guide.
+const MY_TOKENS: [&str; 3] = ["X", "Y", "Z"];
+
+/// Every order of the three shapes of Rock Paper Scissors.
+const SHAPE_PERMUTATIONS: [[Shape; 3]; 6] = [
+    [Shape::ROCK, Shape::PAPER, Shape::SCISSORS],
+    [Shape::ROCK, Shape::SCISSORS, Shape::PAPER],
+    [Shape::PAPER, Shape::ROCK, Shape::SCISSORS],
+    [Shape::PAPER, Shape::SCISSORS, Shape::ROCK],
+    [Shape::SCISSORS, Shape::ROCK, Shape::PAPER],
+    [Shape::SCISSORS, Shape::PAPER, Shape::ROCK],
+];
+
+/// A way of reading the second column of the puzzle's strategy guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interpretation {
+    /// X, Y and Z are the shapes to play, in that order.
+    Shapes([Shape; 3]),
+
+    /// X, Y and Z mean lose, draw and win, as the elf explained.
+    Goals,
+}
+
+impl Interpretation {
+    /// Every interpretation: the six ways of assigning shapes to X, Y and Z, then the goals.
+    pub fn all() -> impl Iterator<Item = Interpretation> {
+        SHAPE_PERMUTATIONS
+            .into_iter()
+            .map(Interpretation::Shapes)
+            .chain([Interpretation::Goals])
+    }
+
+    /// The game and strategy that read a strategy guide this way.
+    fn game(&self) -> (Game, Strategy) {
+        let mut tokens = TokenMapping::puzzle();
+        let strategy = match self {
+            Interpretation::Shapes(shapes) => {
+                for (token, &shape) in MY_TOKENS.iter().zip(shapes) {
+                    tokens = tokens.my_shape(token, shape);
+                }
+                Strategy::Mine
+            }
+            Interpretation::Goals => Strategy::Elf,
+        };
+
+        (
+            Game {
+                ruleset: Ruleset::rock_paper_scissors(),
+                tokens,
             },
-        }
+            strategy,
+        )
     }
 }
 
-pub fn calculated_rock_paper_scissors_tournament_score_with_my_strategy(input: &str) -> u32 {
-    let mut total_score = 0;
+impl Display for Interpretation {
+    /// Prints the meaning of each token, e.g. `X=Rock, Y=Paper, Z=Scissors`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ruleset = Ruleset::rock_paper_scissors();
+        let meanings = match self {
+            Interpretation::Shapes(shapes) => {
+                shapes.map(|shape| ruleset.shape_name(shape).unwrap_or("?").to_string())
+            }
+            Interpretation::Goals => {
+                [Goal::Lose, Goal::Draw, Goal::Win].map(|goal| format!("{goal:?}"))
+            }
+        };
 
-    for line in input.lines() {
-        if line.is_empty() {
-            continue;
-        }
+        let mappings = MY_TOKENS
+            .iter()
+            .zip(meanings)
+            .map(|(token, meaning)| format!("{token}={meaning}"))
+            .collect::<Vec<String>>();
+        write!(f, "{}", mappings.join(", "))
+    }
+}
+
+/// The total score a strategy guide gives under one interpretation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decoding {
+    pub interpretation: Interpretation,
+    pub total_score: u32,
+}
+
+/// Scores a strategy guide under every interpretation of its second column, to help work out
+/// what the elf meant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoder {
+    decodings: Vec<Decoding>,
+}
+
+impl Decoder {
+    /// Plays the strategy guide under every [`Interpretation`].
+    pub fn decode(input: &str) -> Result<Self, Error> {
+        let guide = parse_guide(input)?;
+        let decodings = Interpretation::all()
+            .map(|interpretation| {
+                let (game, strategy) = interpretation.game();
+                let tournament = Tournament::play(&guide, strategy, &game)?;
+
+                Ok(Decoding {
+                    interpretation,
+                    total_score: tournament.total_score(),
+                })
+            })
+            .collect::<Result<Vec<Decoding>, Error>>()?;
+
+        Ok(Decoder { decodings })
+    }
+
+    /// The score under each interpretation, in the order of [`Interpretation::all`].
+    pub fn decodings(&self) -> &[Decoding] {
+        &self.decodings
+    }
+
+    /// The interpretations that give the target score.
+    pub fn consistent_with(&self, target_score: u32) -> impl Iterator<Item = Interpretation> + '_ {
+        self.decodings
+            .iter()
+            .filter(move |decoding| decoding.total_score == target_score)
+            .map(|decoding| decoding.interpretation)
+    }
+}
+
+/// Limits on the moves I can make when planning a counter-strategy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// The exact number of rounds I must lose, if any.
+    pub num_losses: Option<usize>,
 
-        let round = Round::try_from(line).unwrap();
-        total_score += round.my_score_my_strategy;
+    /// The most times in a row I may play the same shape, if any.
+    pub max_repeats: Option<usize>,
+}
+
+impl Constraints {
+    /// Requires losing exactly `num_losses` rounds.
+    pub fn losing_exactly(mut self, num_losses: usize) -> Self {
+        self.num_losses = Some(num_losses);
+        self
+    }
+
+    /// Forbids playing the same shape more than `max_repeats` times in a row.
+    pub fn repeating_at_most(mut self, max_repeats: usize) -> Self {
+        self.max_repeats = Some(max_repeats);
+        self
     }
+}
+
+/// Where a counter-strategy stands after some rounds, as far as the constraints care.
+///
+/// Only what a constraint needs is tracked, so without constraints there is a single state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct PlanState {
+    num_losses: usize,
+    last_shape: Option<Shape>,
+    num_repeats: usize,
+}
 
-    total_score
+/// The best way of reaching a [`PlanState`] after some rounds.
+#[derive(Debug, Clone, Copy)]
+struct PlanStep {
+    total_score: u32,
+    previous: PlanState,
+    my_shape: Shape,
 }
 
-pub fn calculated_rock_paper_scissors_tournament_score_with_elf_strategy(input: &str) -> u32 {
-    let mut total_score = 0;
+impl Tournament {
+    /// Plans the highest scoring moves against the opponent's column of a strategy guide, using
+    /// dynamic programming over the outcome of each round.
+    ///
+    /// Only the first token of each line is read. Returns an [`Error::Unsolvable`] error when
+    /// no moves meet the constraints.
+    pub fn best_response(
+        input: &str,
+        game: &Game,
+        constraints: Constraints,
+    ) -> Result<Self, Error> {
+        let their_shapes = (1..)
+            .zip(input.lines())
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_number, line)| {
+                let token = line.split(' ').next().unwrap_or_default();
+                let their_shape = game
+                    .tokens
+                    .parse_their_shape(token)
+                    .map_err(|reason| Error::parse(2, line_number, line, reason))?;
+
+                Ok((line_number, their_shape))
+            })
+            .collect::<Result<Vec<(usize, Shape)>, Error>>()?;
 
-    for line in input.lines() {
-        if line.is_empty() {
-            continue;
+        let ruleset = &game.ruleset;
+        let start = PlanState {
+            num_losses: 0,
+            last_shape: None,
+            num_repeats: 0,
+        };
+
+        // The best step into each reachable state, after each round.
+        let mut layers: Vec<BTreeMap<PlanState, PlanStep>> = Vec::new();
+
+        for &(_, their_shape) in &their_shapes {
+            let mut layer = BTreeMap::new();
+            let previous_states = match layers.last() {
+                Some(previous_layer) => previous_layer
+                    .iter()
+                    .map(|(&state, step)| (state, step.total_score))
+                    .collect(),
+                None => vec![(start, 0)],
+            };
+
+            for (previous, total_score) in previous_states {
+                for my_shape in ruleset.shapes() {
+                    let Some(state) = previous.play(
+                        my_shape,
+                        ruleset.outcome(my_shape, their_shape),
+                        &constraints,
+                    ) else {
+                        continue;
+                    };
+
+                    let step = PlanStep {
+                        total_score: total_score + ruleset.score(my_shape, their_shape),
+                        previous,
+                        my_shape,
+                    };
+                    layer
+                        .entry(state)
+                        .and_modify(|best: &mut PlanStep| {
+                            if step.total_score > best.total_score {
+                                *best = step;
+                            }
+                        })
+                        .or_insert(step);
+                }
+            }
+
+            layers.push(layer);
         }
 
-        let round = Round::try_from(line).unwrap();
-        total_score += round.my_score_elf_strategy;
+        // Pick the best final state, then walk back through the steps that reached it.
+        let mut state = match layers.last() {
+            Some(last_layer) => last_layer
+                .iter()
+                .filter(|(state, _)| {
+                    constraints
+                        .num_losses
+                        .is_none_or(|num_losses| state.num_losses == num_losses)
+                })
+                .max_by_key(|(_, step)| step.total_score)
+                .map(|(&state, _)| state)
+                .ok_or_else(|| Error::unsolvable(2, "no moves meet the constraints"))?,
+            None if constraints.num_losses.unwrap_or(0) == 0 => start,
+            None => return Err(Error::unsolvable(2, "no moves meet the constraints")),
+        };
+
+        let mut my_shapes = Vec::with_capacity(layers.len());
+        for layer in layers.iter().rev() {
+            let step = layer[&state];
+            my_shapes.push(step.my_shape);
+            state = step.previous;
+        }
+        my_shapes.reverse();
+
+        let rounds = their_shapes
+            .into_iter()
+            .zip(my_shapes)
+            .map(|((line_number, their_shape), my_shape)| Round {
+                line_number,
+                their_shape,
+                my_shape,
+                outcome: ruleset.outcome(my_shape, their_shape),
+                score: ruleset.score(my_shape, their_shape),
+            })
+            .collect();
+
+        Ok(Tournament {
+            rounds,
+            ruleset: ruleset.clone(),
+        })
+    }
+}
+
+impl PlanState {
+    /// The state after playing another round, or `None` if that would break a constraint.
+    fn play(&self, my_shape: Shape, outcome: Goal, constraints: &Constraints) -> Option<Self> {
+        let mut next = *self;
+
+        if let Some(max_losses) = constraints.num_losses {
+            if outcome == Goal::Lose {
+                next.num_losses += 1;
+            }
+            if next.num_losses > max_losses {
+                return None;
+            }
+        }
+
+        if let Some(max_repeats) = constraints.max_repeats {
+            next.num_repeats = if self.last_shape == Some(my_shape) {
+                self.num_repeats + 1
+            } else {
+                1
+            };
+            next.last_shape = Some(my_shape);
+            if next.num_repeats > max_repeats {
+                return None;
+            }
+        }
+
+        Some(next)
     }
+}
+
+pub fn calculated_rock_paper_scissors_tournament_score_with_my_strategy(
+    input: &str,
+) -> Result<u32, Error> {
+    Ok(Tournament::parse(input, Strategy::Mine)?.total_score())
+}
 
-    total_score
+pub fn calculated_rock_paper_scissors_tournament_score_with_elf_strategy(
+    input: &str,
+) -> Result<u32, Error> {
+    Ok(Tournament::parse(input, Strategy::Elf)?.total_score())
 }
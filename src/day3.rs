@@ -1,3 +1,11 @@
+use std::fmt::Display;
+
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -13,11 +21,144 @@ CrZsJsPPZsGzwwsLwLmpwMDw
 
             ";
 
-        let answer = get_misplaced_item_priority_sum(input);
+        let answer = get_misplaced_item_priority_sum(input).unwrap();
 
         assert_eq!(answer, 157);
     }
 
+    #[test]
+    fn get_misplaced_item_priority_sum_reports_items_that_are_not_letters() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+ab1cd1
+";
+
+        let error = get_misplaced_item_priority_sum(input).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "day 3, line 2: invalid item type '1': \"ab1cd1\""
+        );
+    }
+
+    #[test]
+    fn rucksack_intersects_its_compartments() {
+        let rucksack = Rucksack::parse("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+
+        let [first_compartment, second_compartment] = rucksack.compartments();
+        assert_eq!(
+            first_compartment.items(&Letters).collect::<String>(),
+            "gprtvwJW"
+        );
+        assert!(second_compartment.contains('F', &Letters));
+        assert_eq!(
+            rucksack
+                .shared_items()
+                .items(&Letters)
+                .collect::<Vec<char>>(),
+            vec!['p']
+        );
+        assert_eq!(rucksack.misplaced_item_priority(), Ok(16));
+        assert_eq!(rucksack.contents().len(), 14);
+    }
+
+    #[test]
+    fn rucksack_parse_reports_typed_errors() {
+        assert_eq!(Rucksack::parse("aébé"), Err(ItemError::InvalidItem('é')));
+        assert_eq!(Rucksack::parse("abc"), Err(ItemError::OddLength(3)));
+        assert_eq!(
+            Rucksack::parse("abcd").and_then(|rucksack| rucksack.misplaced_item_priority()),
+            Err(ItemError::MissingDuplicate)
+        );
+
+        let error = get_misplaced_item_priority_sum("abca\nabc\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 3, line 2: rucksack has an odd number of items (3): \"abc\""
+        );
+    }
+
+    #[test]
+    fn rucksacks_can_use_a_custom_alphabet() -> Result<(), Error> {
+        let greek = Alphabet::new("αβγδ").unwrap();
+        let rucksack = Rucksack::parse_with("αβγβ", &greek).unwrap();
+
+        assert_eq!(rucksack.misplaced_item_priority(), Ok(2));
+        assert_eq!(
+            get_misplaced_item_priority_sum_with("αβγβ\nδγγα\n", &greek)?,
+            5
+        );
+        assert_eq!(find_badges("αβ\nβγ\n", 2, &greek)?[0].badge, 'β');
+        assert_eq!(
+            Alphabet::new("αβα"),
+            Err(AlphabetError::RepeatedItemType('α'))
+        );
+        assert_eq!(
+            Alphabet::new(&"a".repeat(65)),
+            Err(AlphabetError::TooManyItemTypes(65))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_badges_returns_the_badge_of_each_group() -> Result<(), Error> {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+        let groups = find_badges(input, GROUP_SIZE, &Letters)?;
+
+        assert_eq!(
+            groups,
+            vec![
+                Group {
+                    line_numbers: vec![1, 2, 3],
+                    badge: 'r',
+                    priority: 18
+                },
+                Group {
+                    line_numbers: vec![5, 6, 7],
+                    badge: 'Z',
+                    priority: 52
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_badges_reports_groups_without_exactly_one_badge() {
+        let Err(error) = find_badges("abc\nady\nxyz\n", 2, &Letters) else {
+            panic!("Expected an error");
+        };
+        assert_eq!(
+            error.to_string(),
+            "day 3, line 3: incomplete group: expected 2 rucksacks, found 1: \"xyz\""
+        );
+
+        let Err(error) = find_badges("abc\nabd\n", 2, &Letters) else {
+            panic!("Expected an error");
+        };
+        assert_eq!(
+            error.to_string(),
+            "day 3, line 1: group has 2 common item types: ab: \"abc\""
+        );
+
+        let Err(error) = find_badges("abc\nxyz\n", 2, &Letters) else {
+            panic!("Expected an error");
+        };
+        assert_eq!(
+            error.to_string(),
+            "day 3, line 1: group has no common item type: \"abc\""
+        );
+    }
+
     #[test]
     fn get_badge_priority_sum_returns_the_correct_value() {
         let input = "vJrwpWtwJgWrhcsFMMfFFhFp
@@ -29,7 +170,7 @@ CrZsJsPPZsGzwwsLwLmpwMDw
 
 ";
 
-        let answer = get_badge_priority_sum(input);
+        let answer = get_badge_priority_sum(input).unwrap();
 
         assert_eq!(answer, 70);
     }
@@ -37,131 +178,468 @@ CrZsJsPPZsGzwwsLwLmpwMDw
 
 pub mod part1 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day3.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(3)?;
+
+        let answer = Day3.part1(&input)?;
 
-        let misplaced_item_priority_sum = get_misplaced_item_priority_sum(&input);
+        println!("misplaced_item_priority_sum = {answer}");
 
-        println!("misplaced_item_priority_sum = {misplaced_item_priority_sum:#?}");
+        Ok(())
     }
 }
 
 pub mod part2 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day3.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(3)?;
 
-        let badge_priority_sum = get_badge_priority_sum(&input);
+        let answer = Day3.part2(&input)?;
 
-        println!("badge_priority_sum = {badge_priority_sum:#?}");
+        println!("badge_priority_sum = {answer}");
+
+        Ok(())
+    }
+}
+
+/// Rucksack Reorganization.
+pub struct Day3;
+
+impl Puzzle for Day3 {
+    type Input<'a> = Vec<RucksackLine<'a>>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<RucksackLine<'a>>, Error> {
+        parse_rucksack_lines(input, &Letters)
+    }
+
+    fn solve_part1(&self, rucksacks: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(sum_misplaced_item_priorities(rucksacks)?.into())
     }
 
+    fn solve_part2(&self, rucksacks: &Self::Input<'_>) -> Result<Answer, Error> {
+        let badge_priority_sum = group_badges(rucksacks, GROUP_SIZE, &Letters)?
+            .iter()
+            .map(|group| group.priority as u32)
+            .sum::<u32>();
+
+        Ok(badge_priority_sum.into())
+    }
 }
 
-pub fn get_misplaced_item_priority_sum(input: &str) -> u32 {
-    let mut misplaced_item_priority_sum: u32 = 0;
+/// Why a rucksack's items could not be sorted out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ItemError {
+    /// An item has no priority in the priority table.
+    #[error("invalid item type {0:?}")]
+    InvalidItem(char),
 
-    for line in input.lines() {
-        let line = line.trim();
+    /// A rucksack can not be split into two equally sized compartments.
+    #[error("rucksack has an odd number of items ({0})")]
+    OddLength(usize),
 
-        if line.is_empty() {
-            continue;
-        }
+    /// No item type is in both compartments of a rucksack.
+    #[error("no item type is in both compartments")]
+    MissingDuplicate,
+}
 
-        let compartment_size = line.len() / 2;
+/// Gives each item type a priority between 1 and 64, so that sets of them fit in an [`ItemSet`].
+pub trait PriorityTable {
+    /// The priority of an item type, or `None` if it isn't a valid item type.
+    fn priority(&self, item: char) -> Option<u8>;
 
-        let first_compartment = &line[..compartment_size];
-        let second_compartment = &line[compartment_size..];
+    /// The item type with the given priority.
+    fn item_type(&self, priority: u8) -> Option<char>;
+}
 
-        let matching_item_type =
-            find_matching_item_type(first_compartment, second_compartment).unwrap();
+/// The puzzle's priorities: `a` to `z` are 1 to 26, and `A` to `Z` are 27 to 52.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Letters;
 
-        let misplaced_item_priority = get_item_priority(matching_item_type);
+impl PriorityTable for Letters {
+    fn priority(&self, item: char) -> Option<u8> {
+        get_item_priority(item)
+    }
 
-        misplaced_item_priority_sum += misplaced_item_priority as u32;
+    fn item_type(&self, priority: u8) -> Option<char> {
+        get_item_type(priority)
     }
+}
 
-    misplaced_item_priority_sum
+/// Why a list of item types can't be used as a priority table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum AlphabetError {
+    /// There are more item types than fit in an [`ItemSet`].
+    #[error("an alphabet can have at most 64 item types, but has {0}")]
+    TooManyItemTypes(usize),
+
+    /// An item type is listed more than once, so it would have two priorities.
+    #[error("item type {0:?} is repeated in the alphabet")]
+    RepeatedItemType(char),
 }
 
-fn get_item_priority(item: char) -> u8 {
-    match item as u8 {
-        b'a'..=b'z' => (item as u8) - b'a' + 1,
-        b'A'..=b'Z' => (item as u8) - b'A' + 27,
-        _ => panic!("Item is not a letter!"),
-    }
+/// A custom list of item types, in priority order starting at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    item_types: Vec<char>,
 }
 
-fn find_matching_item_type<'a>(a: &'a str, b: &'a str) -> Result<char, &'static str> {
-    for item_type in a.chars() {
-        if b.contains(item_type) {
-            return Ok(item_type);
+impl Alphabet {
+    /// Creates a priority table from up to 64 different item types, lowest priority first.
+    pub fn new(item_types: &str) -> Result<Self, AlphabetError> {
+        let item_types = item_types.chars().collect::<Vec<char>>();
+
+        if item_types.len() > 64 {
+            return Err(AlphabetError::TooManyItemTypes(item_types.len()));
+        }
+        if let Some(i) = (1..item_types.len()).find(|&i| item_types[..i].contains(&item_types[i])) {
+            return Err(AlphabetError::RepeatedItemType(item_types[i]));
         }
+
+        Ok(Alphabet { item_types })
     }
+}
+
+impl PriorityTable for Alphabet {
+    fn priority(&self, item: char) -> Option<u8> {
+        let index = self.item_types.iter().position(|&c| c == item)?;
 
-    Err("No Matching Item Type Found")
+        Some(index as u8 + 1)
+    }
+
+    fn item_type(&self, priority: u8) -> Option<char> {
+        let index = (priority as usize).checked_sub(1)?;
+
+        self.item_types.get(index).copied()
+    }
 }
 
-pub fn get_badge_priority_sum(input: &str) -> u32 {
-    let mut badge_priority_sum = 0;
+/// A set of item types, stored as a mask with bit `priority - 1` set for each item type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// Collects item types, which must all be in the priority table.
+    pub fn from_items(
+        items: impl IntoIterator<Item = char>,
+        table: &dyn PriorityTable,
+    ) -> Result<Self, ItemError> {
+        items.into_iter().try_fold(ItemSet::default(), |set, item| {
+            let priority = item_priority(item, table)?;
+
+            Ok(set.union(ItemSet::from_priorities([priority])))
+        })
+    }
 
-    // Fold lines of 3 into groups.
-    let groups = input
-        .lines()
-        .fold(Vec::<Vec<&str>>::new(), |mut collection, line| {
-            match collection.last() {
-                Some(group) if group.len() < 3 => (),
-                _ => collection.push(Vec::<&str>::new()),
-            }
+    /// Collects priorities, which must all be in `1..=64`.
+    fn from_priorities(priorities: impl IntoIterator<Item = u8>) -> Self {
+        ItemSet(
+            priorities
+                .into_iter()
+                .fold(0, |mask, priority| mask | 1 << (priority - 1)),
+        )
+    }
 
-            let group = collection.last_mut().unwrap();
+    /// The item types in both sets.
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
 
-            // Ensure that the shortest string is on top for optimal processing (see below).
-            match group.first() {
-                Some(rucksack) => match rucksack.len() {
-                    existing_len if existing_len > line.len() => group.insert(0, line),
-                    _ => group.push(line),
-                },
-                _ => group.push(line),
-            }
+    /// The item types in either set.
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
 
-            collection
-        });
+    pub fn contains(&self, item: char, table: &dyn PriorityTable) -> bool {
+        ItemSet::from_items([item], table).is_ok_and(|item| self.intersection(item) == item)
+    }
 
-    for group in groups.iter() {
-        // Good 'nuff for now, but isn't foolproof for getting rid of invalid/empty groups.
-        if group.len() < 3 {
-            continue;
-        }
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
 
-        let badge_type = {
-            let mut result = None;
-
-            // Since the first line is the shortest (see fold code above),
-            // loop through it to find the common item type.
-            let smallest_rucksack = group.first().unwrap();
-            for (i, _) in smallest_rucksack.char_indices() {
-                let c = &smallest_rucksack[i..i + 1];
-                if group.get(1).unwrap().contains(&c) && group.get(2).unwrap().contains(&c) {
-                    result = Some(c);
-                    break;
-                }
-            }
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The priority of each item type, from lowest to highest.
+    pub fn priorities(&self) -> impl Iterator<Item = u8> {
+        let mask = self.0;
+
+        (1..=64).filter(move |priority| mask & 1 << (priority - 1) != 0)
+    }
+
+    /// Each item type, in priority order.
+    pub fn items<'a>(&self, table: &'a dyn PriorityTable) -> impl Iterator<Item = char> + 'a {
+        self.priorities()
+            .filter_map(|priority| table.item_type(priority))
+    }
+}
+
+/// The priority of an item type, which must fit in an [`ItemSet`].
+fn item_priority(item: char, table: &dyn PriorityTable) -> Result<u8, ItemError> {
+    table
+        .priority(item)
+        .filter(|priority| (1..=64).contains(priority))
+        .ok_or(ItemError::InvalidItem(item))
+}
+
+/// A line of the puzzle input listing the items in a rucksack, all of which have a priority.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RucksackLine<'a> {
+    line_number: usize,
+    line: &'a str,
+    /// The priority of each item, in the order they are listed. Each is in `1..=64`.
+    priorities: Vec<u8>,
+}
+
+impl<'a> RucksackLine<'a> {
+    /// Looks up the priority of every item on a line.
+    pub fn parse(
+        line_number: usize,
+        line: &'a str,
+        table: &dyn PriorityTable,
+    ) -> Result<Self, ItemError> {
+        let priorities = line
+            .chars()
+            .map(|item| item_priority(item, table))
+            .collect::<Result<Vec<u8>, ItemError>>()?;
+
+        Ok(RucksackLine {
+            line_number,
+            line,
+            priorities,
+        })
+    }
+
+    /// The line of the puzzle input the rucksack came from.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// The items in the rucksack, as listed in the puzzle input.
+    pub fn line(&self) -> &'a str {
+        self.line
+    }
 
-            result
+    /// Splits the items into the rucksack's two compartments.
+    pub fn rucksack(&self) -> Result<Rucksack, ItemError> {
+        let num_items = self.priorities.len();
+        if !num_items.is_multiple_of(2) {
+            return Err(ItemError::OddLength(num_items));
         }
-        .unwrap();
 
-        let badge_priority = get_item_priority(badge_type.chars().next().unwrap());
+        let (first_compartment, second_compartment) = self.priorities.split_at(num_items / 2);
+
+        Ok(Rucksack {
+            compartments: [
+                ItemSet::from_priorities(first_compartment.iter().copied()),
+                ItemSet::from_priorities(second_compartment.iter().copied()),
+            ],
+        })
+    }
+
+    /// The item types anywhere in the rucksack.
+    pub fn contents(&self) -> ItemSet {
+        ItemSet::from_priorities(self.priorities.iter().copied())
+    }
 
-        badge_priority_sum += badge_priority as u32;
+    /// An error about this line.
+    fn error(&self, reason: impl Display) -> Error {
+        Error::parse(3, self.line_number, self.line, reason)
     }
+}
+
+/// Looks up the priority of every item in every rucksack, skipping empty lines.
+pub fn parse_rucksack_lines<'a>(
+    input: &'a str,
+    table: &dyn PriorityTable,
+) -> Result<Vec<RucksackLine<'a>>, Error> {
+    (1..)
+        .zip(input.lines())
+        .map(|(line_number, line)| (line_number, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line_number, line)| {
+            RucksackLine::parse(line_number, line, table)
+                .map_err(|error| Error::parse(3, line_number, line, error))
+        })
+        .collect()
+}
+
+/// A rucksack, split into two equally sized compartments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    compartments: [ItemSet; 2],
+}
 
-    badge_priority_sum
+impl Rucksack {
+    /// Parses a line listing the items in a rucksack, the first half of which are in the first
+    /// compartment, using the puzzle's priorities.
+    pub fn parse(line: &str) -> Result<Self, ItemError> {
+        Self::parse_with(line, &Letters)
+    }
+
+    /// Parses a line listing the items in a rucksack, using any priority table.
+    pub fn parse_with(line: &str, table: &dyn PriorityTable) -> Result<Self, ItemError> {
+        RucksackLine::parse(1, line, table)?.rucksack()
+    }
+
+    /// The item types in each compartment.
+    pub fn compartments(&self) -> [ItemSet; 2] {
+        self.compartments
+    }
+
+    /// The item types anywhere in the rucksack.
+    pub fn contents(&self) -> ItemSet {
+        self.compartments[0].union(self.compartments[1])
+    }
+
+    /// The item types in both compartments, which have been misplaced.
+    pub fn shared_items(&self) -> ItemSet {
+        self.compartments[0].intersection(self.compartments[1])
+    }
+
+    /// The priority of the item type in both compartments.
+    pub fn misplaced_item_priority(&self) -> Result<u8, ItemError> {
+        self.shared_items()
+            .priorities()
+            .next()
+            .ok_or(ItemError::MissingDuplicate)
+    }
+}
+
+pub fn get_misplaced_item_priority_sum(input: &str) -> Result<u32, Error> {
+    get_misplaced_item_priority_sum_with(input, &Letters)
+}
+
+/// Sums the priorities of the misplaced items, using any priority table.
+pub fn get_misplaced_item_priority_sum_with(
+    input: &str,
+    table: &dyn PriorityTable,
+) -> Result<u32, Error> {
+    sum_misplaced_item_priorities(&parse_rucksack_lines(input, table)?)
+}
+
+/// Sums the priorities of the misplaced items in already parsed rucksacks.
+pub fn sum_misplaced_item_priorities(rucksacks: &[RucksackLine<'_>]) -> Result<u32, Error> {
+    let mut misplaced_item_priority_sum: u32 = 0;
+
+    for rucksack_line in rucksacks {
+        let misplaced_item_priority = rucksack_line
+            .rucksack()
+            .and_then(|rucksack| rucksack.misplaced_item_priority())
+            .map_err(|error| rucksack_line.error(error))?;
+
+        misplaced_item_priority_sum += misplaced_item_priority as u32;
+    }
+
+    Ok(misplaced_item_priority_sum)
+}
+
+fn get_item_priority(item: char) -> Option<u8> {
+    match item {
+        'a'..='z' => Some((item as u8) - b'a' + 1),
+        'A'..='Z' => Some((item as u8) - b'A' + 27),
+        _ => None,
+    }
+}
+
+/// The item type with the given priority.
+fn get_item_type(priority: u8) -> Option<char> {
+    match priority {
+        1..=26 => Some((b'a' + priority - 1) as char),
+        27..=52 => Some((b'A' + priority - 27) as char),
+        _ => None,
+    }
+}
+
+/// The number of elves in each group of the puzzle.
+pub const GROUP_SIZE: usize = 3;
+
+/// A group of elves, along with the badge they all carry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    /// The line numbers of the group's rucksacks.
+    pub line_numbers: Vec<usize>,
+
+    /// The only item type in every rucksack of the group.
+    pub badge: char,
+
+    pub priority: u8,
+}
+
+pub fn get_badge_priority_sum(input: &str) -> Result<u32, Error> {
+    Ok(find_badges(input, GROUP_SIZE, &Letters)?
+        .iter()
+        .map(|group| group.priority as u32)
+        .sum())
+}
+
+/// Splits the rucksacks into groups of `group_size` consecutive lines, skipping empty lines, and
+/// finds the badge of each group.
+///
+/// It is an error for the last group to be incomplete, or for a group not to have exactly one
+/// item type in common.
+pub fn find_badges(
+    input: &str,
+    group_size: usize,
+    table: &dyn PriorityTable,
+) -> Result<Vec<Group>, Error> {
+    group_badges(&parse_rucksack_lines(input, table)?, group_size, table)
+}
+
+/// Splits already parsed rucksacks into groups of `group_size` and finds the badge of each group.
+pub fn group_badges(
+    rucksacks: &[RucksackLine<'_>],
+    group_size: usize,
+    table: &dyn PriorityTable,
+) -> Result<Vec<Group>, Error> {
+    if group_size == 0 {
+        return Err(Error::unsolvable(3, "groups need at least one elf"));
+    }
+
+    rucksacks
+        .chunks(group_size)
+        .map(|group| {
+            let group_error = |reason: String| group[0].error(reason);
+
+            if group.len() < group_size {
+                return Err(group_error(format!(
+                    "incomplete group: expected {group_size} rucksacks, found {}",
+                    group.len()
+                )));
+            }
+
+            let common_items = group
+                .iter()
+                .map(RucksackLine::contents)
+                .reduce(ItemSet::intersection)
+                .unwrap_or_default();
+
+            let badge = common_items
+                .items(table)
+                .zip(common_items.priorities())
+                .next();
+            let (badge, priority) = match (badge, common_items.len()) {
+                (Some(badge), 1) => badge,
+                (None, _) => return Err(group_error("group has no common item type".to_string())),
+                (_, num_common_items) => {
+                    return Err(group_error(format!(
+                        "group has {num_common_items} common item types: {}",
+                        common_items.items(table).collect::<String>()
+                    )))
+                }
+            };
+
+            Ok(Group {
+                line_numbers: group
+                    .iter()
+                    .map(|rucksack_line| rucksack_line.line_number)
+                    .collect(),
+                badge,
+                priority,
+            })
+        })
+        .collect()
 }
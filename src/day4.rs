@@ -1,3 +1,17 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+    fmt::Display,
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,11 +27,110 @@ mod tests {
     
 ";
 
-        let answer = get_num_assignments_fully_contains_other_in_pair(input);
+        let answer = get_num_assignments_fully_contains_other_in_pair(input).unwrap();
 
         assert_eq!(answer, 2);
     }
 
+    #[test]
+    fn get_num_overlapping_assignments_reports_malformed_lines() {
+        let error = get_num_overlapping_assignments("2-4,6-8\n2-4;6-8\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 4, line 2: expected two comma separated ranges: \"2-4;6-8\""
+        );
+
+        let error = get_num_overlapping_assignments("2-x,6-8\n").unwrap_err();
+        assert!(matches!(error, Error::Parse { line_number: 1, .. }));
+    }
+
+    #[test]
+    fn section_range_supports_set_operations() {
+        let a: SectionRange = "2-6".parse().unwrap();
+        let b: SectionRange = "4-8".parse().unwrap();
+        let c: SectionRange = "9-9".parse().unwrap();
+
+        assert_eq!(a.len(), 5);
+        assert!(!a.contains(&b));
+        assert!(a.contains(&"3-4".parse().unwrap()));
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&c));
+        assert_eq!(a.intersection(&b), Some(SectionRange::new(4, 6).unwrap()));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.union(&b), Some(SectionRange::new(2, 8).unwrap()));
+        assert_eq!(b.union(&c), Some(SectionRange::new(4, 9).unwrap()));
+        assert_eq!(a.union(&c), None);
+        assert_eq!(a.to_string(), "2-6");
+    }
+
+    #[test]
+    fn section_range_rejects_reversed_ranges() {
+        assert_eq!(
+            "8-2".parse::<SectionRange>(),
+            Err(RangeError::Reversed { start: 8, end: 2 })
+        );
+
+        let error = get_num_overlapping_assignments("2-4,8-2\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 4, line 1: range 8-2 is reversed: \"2-4,8-2\""
+        );
+    }
+
+    #[test]
+    fn camp_analyses_the_whole_assignment_list() -> Result<(), Error> {
+        let camp = Camp::parse("1-2,4-4\n6-7,9-9\n\n2-3,9-10\n")?;
+        let ranges = |ranges: Vec<SectionRange>| {
+            ranges
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(camp.assignments().len(), 6);
+        assert_eq!(ranges(camp.coverage()), vec!["1-4", "6-7", "9-10"]);
+        assert_eq!(ranges(camp.uncovered_sections()), vec!["5-5", "8-8"]);
+        assert_eq!(
+            ranges(camp.sections_assigned_to_more_than(1)),
+            vec!["2-2", "9-9"]
+        );
+        assert_eq!(camp.sections_assigned_to_more_than(2), vec![]);
+        assert_eq!(camp.overlapping_line_pairs(), vec![(1, 4), (2, 4)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn count_assignments_skips_malformed_lines_in_lenient_mode() -> Result<(), Error> {
+        let input = "2-4,6-8\n2-4;6-8\n2-8,3-7\n5-x,7-9\n2-6\n8-2,1-1\n";
+
+        let counts = count_assignments(input, ParseMode::Lenient)?;
+
+        assert_eq!(counts.num_fully_containing, 1);
+        assert_eq!(counts.num_overlapping, 1);
+        let skipped_lines = counts
+            .skipped_lines
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            skipped_lines,
+            vec![
+                "day 4, line 2: expected two comma separated ranges: \"2-4;6-8\"",
+                "day 4, line 4: invalid section \"x\": invalid digit found in string: \"5-x,7-9\"",
+                "day 4, line 5: expected two comma separated ranges: \"2-6\"",
+                "day 4, line 6: range 8-2 is reversed: \"8-2,1-1\"",
+            ]
+        );
+
+        let Err(error) = count_assignments(input, ParseMode::Strict) else {
+            panic!("Expected an error");
+        };
+        assert!(matches!(error, Error::Parse { line_number: 2, .. }));
+
+        Ok(())
+    }
+
     #[test]
     fn get_num_overlapping_assignments_returns_the_correct_answer() {
         let input = "2-4,6-8
@@ -29,7 +142,7 @@ mod tests {
     
 ";
 
-        let answer = get_num_overlapping_assignments(input);
+        let answer = get_num_overlapping_assignments(input).unwrap();
 
         assert_eq!(answer, 4);
     }
@@ -37,107 +150,400 @@ mod tests {
 
 pub mod part1 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day4.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(4)?;
+
+        let answer = Day4.part1(&input)?;
 
-        let answer = get_num_assignments_fully_contains_other_in_pair(&input);
+        println!("part 1 answer = {answer}");
 
-        println!("part 1 answer = {answer:#?}");
+        Ok(())
     }
 }
 
 pub mod part2 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day4.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(4)?;
 
-        let answer = get_num_overlapping_assignments(&input);
+        let answer = Day4.part2(&input)?;
 
-        println!("part 2 answer = {answer:#?}");
+        println!("part 2 answer = {answer}");
+
+        Ok(())
     }
 }
 
-pub fn get_num_assignments_fully_contains_other_in_pair(assignment_list: &str) -> u32 {
-    let mut num_assignments_fully_contains_other = 0;
+/// Camp Cleanup.
+pub struct Day4;
+
+impl Puzzle for Day4 {
+    type Input<'a> = Vec<[Assignment; 2]>;
+
+    fn parse(&self, input: &str) -> Result<Vec<[Assignment; 2]>, Error> {
+        parse_assignment_pairs(input)
+    }
+
+    fn solve_part1(&self, pairs: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(AssignmentCounts::of(pairs).num_fully_containing.into())
+    }
 
-    let range_contains = |a: (u32, u32), b: (u32, u32)| a.0 <= b.0 && a.1 >= b.1;
-    let parse_range = |input: &str| {
-        let parts: Vec<&str> = input.split('-').collect();
-        if parts.len() != 2 {
-            return Err("Invalid Range");
+    fn solve_part2(&self, pairs: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(AssignmentCounts::of(pairs).num_overlapping.into())
+    }
+}
+
+pub fn get_num_assignments_fully_contains_other_in_pair(
+    assignment_list: &str,
+) -> Result<u32, Error> {
+    Ok(count_assignments(assignment_list, ParseMode::Strict)?.num_fully_containing)
+}
+
+pub fn get_num_overlapping_assignments(assignment_list: &str) -> Result<u32, Error> {
+    Ok(count_assignments(assignment_list, ParseMode::Strict)?.num_overlapping)
+}
+
+/// What to do with lines of the assignment list that can not be parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Fail on the first malformed line.
+    #[default]
+    Strict,
+
+    /// Skip malformed lines, keeping track of why each was skipped.
+    Lenient,
+}
+
+/// The number of pairs where one assignment fully contains the other, and where they overlap.
+#[derive(Debug, Default)]
+pub struct AssignmentCounts {
+    pub num_fully_containing: u32,
+    pub num_overlapping: u32,
+
+    /// The malformed lines skipped in [`ParseMode::Lenient`], in order.
+    pub skipped_lines: Vec<Error>,
+}
+
+impl AssignmentCounts {
+    /// Counts already parsed pairs of assignments.
+    pub fn of(pairs: &[[Assignment; 2]]) -> Self {
+        let mut counts = AssignmentCounts::default();
+        for pair in pairs {
+            counts.count(pair);
         }
 
-        let start = parts.first().unwrap().parse::<u32>().unwrap();
-        let end = parts.get(1).unwrap().parse::<u32>().unwrap();
-        Ok((start, end))
-    };
+        counts
+    }
 
-    for assignment_line in assignment_list.lines() {
-        let (range_a, range_b) = {
-            let parts: Vec<&str> = assignment_line.split(',').collect();
-            if parts.len() != 2 {
-                continue;
-            }
-            let Ok(a) = parse_range(parts.first().unwrap()) else {
-                continue;
-            };
-            let Ok(b) = parse_range(parts.get(1).unwrap()) else {
-                continue;
-            };
-            (a, b)
-        };
+    /// Counts a pair of assignments.
+    fn count(&mut self, [a, b]: &[Assignment; 2]) {
+        if a.range.contains(&b.range) || b.range.contains(&a.range) {
+            self.num_fully_containing += 1;
+        }
+        if a.range.overlaps(&b.range) {
+            self.num_overlapping += 1;
+        }
+    }
+}
+
+/// Counts the pairs of assignments that fully contain or overlap each other, skipping empty
+/// lines.
+pub fn count_assignments(
+    assignment_list: &str,
+    mode: ParseMode,
+) -> Result<AssignmentCounts, Error> {
+    let mut counts = AssignmentCounts::default();
 
-        if range_contains(range_a, range_b) || range_contains(range_b, range_a) {
-            num_assignments_fully_contains_other += 1;
+    for pair in assignment_pairs(assignment_list) {
+        match (pair, mode) {
+            (Ok(pair), _) => counts.count(&pair),
+            (Err(e), ParseMode::Strict) => return Err(e),
+            (Err(e), ParseMode::Lenient) => counts.skipped_lines.push(e),
         }
     }
 
-    num_assignments_fully_contains_other
+    Ok(counts)
 }
 
-pub fn get_num_overlapping_assignments(assignment_list: &str) -> u32 {
-    let mut num_overlapping_assignments = 0;
+/// Why a section range could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RangeError {
+    /// The range isn't two section IDs separated by a dash.
+    #[error("invalid range {0:?}")]
+    Malformed(String),
 
-    let is_in_range = |n, range: (u32, u32)| n >= range.0 && n <= range.1;
-    let range_overlaps = |a: (u32, u32), b: (u32, u32)| {
-        is_in_range(a.0, b) || is_in_range(a.1, b) || is_in_range(b.0, a) || is_in_range(b.1, a)
-    };
-    let parse_range = |input: &str| {
-        let parts: Vec<&str> = input.split('-').collect();
-        if parts.len() != 2 {
-            return Err("Invalid Range");
+    /// A bound of the range isn't a section ID.
+    #[error("invalid section {section:?}: {source}")]
+    InvalidSection {
+        section: String,
+        source: ParseIntError,
+    },
+
+    /// The range ends before it starts.
+    #[error("range {start}-{end} is reversed")]
+    Reversed { start: u32, end: u32 },
+}
+
+/// An inclusive range of section IDs, such as `2-4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SectionRange {
+    start: u32,
+    end: u32,
+}
+
+impl SectionRange {
+    /// Creates a range from `start` to `end` inclusive, which must not be reversed.
+    pub fn new(start: u32, end: u32) -> Result<Self, RangeError> {
+        if start > end {
+            return Err(RangeError::Reversed { start, end });
         }
 
-        let start = parts.first().unwrap().parse::<u32>().unwrap();
-        let end = parts.get(1).unwrap().parse::<u32>().unwrap();
-        Ok((start, end))
-    };
+        Ok(SectionRange { start, end })
+    }
 
-    for assignment_line in assignment_list.lines() {
-        let (range_a, range_b) = {
-            let parts: Vec<&str> = assignment_line.split(',').collect();
-            if parts.len() != 2 {
-                continue;
-            }
-            let Ok(a) = parse_range(parts.first().unwrap()) else {
-                continue;
-            };
-            let Ok(b) = parse_range(parts.get(1).unwrap()) else {
-                continue;
-            };
-            (a, b)
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// The number of sections in the range.
+    pub fn len(&self) -> u64 {
+        (self.end - self.start) as u64 + 1
+    }
+
+    /// Always `false`, since a range has at least one section.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Whether every section of `other` is in this range.
+    pub fn contains(&self, other: &SectionRange) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    pub fn contains_section(&self, section: u32) -> bool {
+        (self.start..=self.end).contains(&section)
+    }
+
+    /// Whether the ranges have any sections in common.
+    pub fn overlaps(&self, other: &SectionRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// The sections in both ranges, if there are any.
+    pub fn intersection(&self, other: &SectionRange) -> Option<SectionRange> {
+        self.overlaps(other).then(|| SectionRange {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// The sections in either range, if together they form a single range, i.e. they overlap or
+    /// are next to each other.
+    pub fn union(&self, other: &SectionRange) -> Option<SectionRange> {
+        let is_adjacent = self.end.checked_add(1) == Some(other.start)
+            || other.end.checked_add(1) == Some(self.start);
+
+        (self.overlaps(other) || is_adjacent).then(|| SectionRange {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+}
+
+impl FromStr for SectionRange {
+    type Err = RangeError;
+
+    /// Parses a range such as `2-4`.
+    fn from_str(input: &str) -> Result<Self, RangeError> {
+        let Some((start, end)) = input.split_once('-') else {
+            return Err(RangeError::Malformed(input.to_string()));
         };
 
-        if range_overlaps(range_a, range_b) {
-            num_overlapping_assignments += 1;
+        let parse_bound = |bound: &str| {
+            bound
+                .trim()
+                .parse::<u32>()
+                .map_err(|source| RangeError::InvalidSection {
+                    section: bound.to_string(),
+                    source,
+                })
+        };
+
+        SectionRange::new(parse_bound(start)?, parse_bound(end)?)
+    }
+}
+
+impl Display for SectionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// One elf's assignment, along with where it came from in the assignment list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment {
+    /// The line of the assignment list with the elf's pair.
+    pub line_number: usize,
+
+    pub range: SectionRange,
+}
+
+/// Every assignment in the camp, for analysing the whole assignment list at once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Camp {
+    assignments: Vec<Assignment>,
+}
+
+impl Camp {
+    /// Parses every pair of assignments, skipping empty lines.
+    pub fn parse(assignment_list: &str) -> Result<Self, Error> {
+        let assignments = parse_assignment_pairs(assignment_list)?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok(Camp { assignments })
+    }
+
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    /// The sections assigned to anyone, merged into disjoint ranges in order.
+    pub fn coverage(&self) -> Vec<SectionRange> {
+        self.sections_where(|num_elves| num_elves > 0)
+    }
+
+    /// The sections between the first and last assigned sections that nobody is assigned to.
+    pub fn uncovered_sections(&self) -> Vec<SectionRange> {
+        self.sections_where(|num_elves| num_elves == 0)
+    }
+
+    /// The sections assigned to more than `max_elves` elves.
+    pub fn sections_assigned_to_more_than(&self, max_elves: usize) -> Vec<SectionRange> {
+        self.sections_where(|num_elves| num_elves > max_elves)
+    }
+
+    /// Every pair of lines with assignments that overlap, each as `(earlier, later)` line
+    /// numbers in order. Pairs that only overlap within a line aren't included.
+    ///
+    /// Sweeps through the assignments in order of their first section, keeping the assignments
+    /// that are still going in a heap ordered by their last section. Every assignment still
+    /// going when another starts overlaps it, so this takes O(n log n) time plus the number of
+    /// overlaps.
+    pub fn overlapping_line_pairs(&self) -> Vec<(usize, usize)> {
+        let mut assignments = self.assignments.clone();
+        assignments.sort_by_key(|assignment| assignment.range);
+
+        let mut pairs = BTreeSet::new();
+        let mut ongoing = BinaryHeap::<Reverse<(u32, usize)>>::new();
+
+        for assignment in assignments {
+            while let Some(Reverse((end, _))) = ongoing.peek() {
+                if *end >= assignment.range.start() {
+                    break;
+                }
+                ongoing.pop();
+            }
+
+            for &Reverse((_, line_number)) in &ongoing {
+                if line_number != assignment.line_number {
+                    pairs.insert((
+                        line_number.min(assignment.line_number),
+                        line_number.max(assignment.line_number),
+                    ));
+                }
+            }
+
+            ongoing.push(Reverse((assignment.range.end(), assignment.line_number)));
+        }
+
+        pairs.into_iter().collect()
+    }
+
+    /// The ranges of sections, between the first and last assigned sections, where the number
+    /// of elves assigned matches the predicate.
+    ///
+    /// Sweeps through the points where the number of elves changes, in order.
+    fn sections_where(&self, predicate: impl Fn(usize) -> bool) -> Vec<SectionRange> {
+        let mut changes = BTreeMap::<u64, isize>::new();
+        for assignment in &self.assignments {
+            *changes.entry(assignment.range.start() as u64).or_default() += 1;
+            *changes
+                .entry(assignment.range.end() as u64 + 1)
+                .or_default() -= 1;
         }
+
+        let mut ranges = Vec::new();
+        let mut num_elves = 0;
+        let mut range_start = None;
+
+        // The number of elves only changes at each point, so it holds until the next one. After
+        // the last point nobody is assigned, so a range still going there is never finished.
+        for (section, change) in changes {
+            num_elves += change;
+
+            match (range_start, predicate(num_elves as usize)) {
+                (None, true) => range_start = Some(section),
+                (Some(start), false) => {
+                    ranges.push(SectionRange {
+                        start: start as u32,
+                        end: (section - 1) as u32,
+                    });
+                    range_start = None;
+                }
+                _ => (),
+            }
+        }
+
+        ranges
     }
+}
+
+/// Parses every pair of assignments, skipping empty lines.
+pub fn parse_assignment_pairs(assignment_list: &str) -> Result<Vec<[Assignment; 2]>, Error> {
+    assignment_pairs(assignment_list).collect()
+}
+
+/// Parses each non-empty line of the assignment list into a pair of assignments.
+fn assignment_pairs(
+    assignment_list: &str,
+) -> impl Iterator<Item = Result<[Assignment; 2], Error>> + '_ {
+    (1..)
+        .zip(assignment_list.lines())
+        .filter(|(_, assignment_line)| !assignment_line.trim().is_empty())
+        .map(|(line_number, assignment_line)| {
+            let (range_a, range_b) = parse_assignment_pair(assignment_line, line_number)?;
+
+            Ok([range_a, range_b].map(|range| Assignment { line_number, range }))
+        })
+}
+
+/// Parses a line such as `2-4,6-8` into a pair of section ranges.
+fn parse_assignment_pair(
+    assignment_line: &str,
+    line_number: usize,
+) -> Result<(SectionRange, SectionRange), Error> {
+    let parse_error = |reason: String| Error::parse(4, line_number, assignment_line, reason);
+
+    let parts: Vec<&str> = assignment_line.split(',').collect();
+    let [a, b] = parts[..] else {
+        return Err(parse_error(
+            "expected two comma separated ranges".to_string(),
+        ));
+    };
+
+    let parse_range = |range: &str| {
+        range
+            .parse::<SectionRange>()
+            .map_err(|e| parse_error(e.to_string()))
+    };
 
-    num_overlapping_assignments
+    Ok((parse_range(a)?, parse_range(b)?))
 }
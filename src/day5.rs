@@ -1,6 +1,22 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    num::NonZeroUsize,
+    ops::RangeInclusive,
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn get_message_from_rearranged_crates_returns_the_correct_answer() {
@@ -16,18 +32,27 @@ move 2 from 2 to 1
 move 1 from 1 to 2
 
 ";
-        let answer = get_message_from_rearranged_crates(
-            input,
-            &Settings {
-                move_multiple_crates_at_once: false,
-            },
-        );
+        let answer = get_message_from_rearranged_crates(input, &Crane::CRATE_MOVER_9000).unwrap();
 
         assert_eq!(answer, "CMZ");
     }
 
     #[test]
-    fn get_message_from_rearranged_crates_moving_multiple_crates_at_once_returns_the_correct_answer() {
+    fn crates_display_as_the_puzzle_drawing() -> Result<(), Error> {
+        let drawing = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 ";
+
+        let (crates, _) = parse_initial_state_and_instructions(drawing)?;
+
+        assert_eq!(crates.to_drawing(), drawing);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cranes_move_crates_in_lifts_and_count_their_cost() -> Result<(), Error> {
         let input = "
     [D]    
 [N] [C]    
@@ -38,67 +63,409 @@ move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2
+";
+        let (crates, instructions) = parse_initial_state_and_instructions(input)?;
+        let rearranged = |crane: Crane| {
+            let mut crates = crates.clone();
+            let stats = crates
+                .try_rearrange(&instructions, &crane)
+                .map_err(|e| Error::unsolvable(5, e))?;
+            Ok::<_, Error>((crates, stats))
+        };
+
+        let (two_at_a_time, stats) = rearranged(Crane::with_lift_capacity(2).unwrap())?;
+        assert_eq!(two_at_a_time.stacks[2], vec!["P", "N", "D", "Z"]);
+        assert_eq!(two_at_a_time.get_message(), "MCZ");
+        assert_eq!(
+            stats,
+            CraneStats {
+                num_instructions: 4,
+                num_lifts: 5,
+                num_crates_moved: 7,
+            }
+        );
+        let cost_model = CostModel {
+            per_lift: 10,
+            per_crate: 1,
+        };
+        assert_eq!(stats.cost(&cost_model), 57);
+
+        let (height_limited, stats) = rearranged(Crane::with_height_limit(4))?;
+        assert_eq!(height_limited.get_message(), "MCD");
+        assert_eq!(stats.num_lifts, 4);
+        assert_eq!(rearranged(Crane::CRATE_MOVER_9000)?.1.num_lifts, 7);
+        assert_eq!(Crane::with_lift_capacity(0), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_rearrange_reports_the_instruction_that_failed() -> Result<(), Error> {
+        let (crates, _) = parse_initial_state_and_instructions("[A]    \n 1   2 \n")?;
+        let try_rearrange = |instructions: &str, crane: Crane| {
+            let (_, instructions) =
+                parse_initial_state_and_instructions(&format!(" 1 \n\n{instructions}"))?;
+            let mut crates = crates.clone();
+            let result = crates.try_rearrange(&instructions, &crane);
+            Ok::<_, Error>((crates, result))
+        };
+
+        let (crates_after_error, result) = try_rearrange(
+            "move 1 from 1 to 2\nmove 2 from 2 to 1\n",
+            Crane::OneAtATime,
+        )?;
+        let Err(error) = result else {
+            panic!("Expected an error");
+        };
+        assert_eq!(
+            error.to_string(),
+            "instruction 2 (move 2 from 2 to 1): stack 2 only has 1 crate(s), but 2 need moving"
+        );
+        assert_eq!(crates_after_error.get_message(), "-A");
+
+        let (_, result) = try_rearrange("move 1 from 1 to 3\n", Crane::OneAtATime)?;
+        assert_eq!(
+            result.map_err(|error| error.reason),
+            Err(MoveError::UnknownStack { stack_number: 3 })
+        );
 
+        let (_, result) = try_rearrange("move 1 from 1 to 2\n", Crane::with_height_limit(0))?;
+        assert_eq!(
+            result.map_err(|error| error.reason),
+            Err(MoveError::StackTooTall {
+                stack_number: 2,
+                max_height: 0
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn crates_steps_yields_the_crates_after_each_move() -> Result<(), Error> {
+        let input = "
+    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
 ";
-        let answer = get_message_from_rearranged_crates(
-            input,
-            &Settings {
-                move_multiple_crates_at_once: true,
-            },
+        let (crates, instructions) = parse_initial_state_and_instructions(input)?;
+        let crane = Crane::CRATE_MOVER_9000;
+
+        let history = crates
+            .steps(&instructions, &crane)
+            .collect::<Result<Vec<Snapshot>, RearrangeError>>()
+            .unwrap();
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].instruction_number, 1);
+        assert_eq!(history[0].moved, vec![CratePosition { stack: 0, level: 2 }]);
+        assert_eq!(
+            history[1].crates.to_drawing(),
+            "        [Z]
+        [N]
+    [C] [D]
+    [M] [P]
+ 1   2   3 "
+        );
+        assert_eq!(
+            history[0].crates.to_highlighted_drawing(&history[0].moved),
+            "\x1b[1;33m[D]\x1b[0m        
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 "
         );
 
+        Ok(())
+    }
+
+    #[test]
+    fn animate_redraws_the_crates_after_each_move() -> Result<(), Error> {
+        let (crates, instructions) =
+            parse_initial_state_and_instructions("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\n")?;
+        let mut out = Vec::new();
+
+        animate(
+            &crates,
+            &instructions,
+            &Crane::CRATE_MOVER_9000,
+            &Animation::default(),
+            &mut out,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[2J\x1b[H[A] [B]\n 1   2 \n\x1b[2J\x1b[H    [A]\n    [B]\n 1   2 \nmove 1 of 1\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn animate_reports_the_instruction_that_failed() -> Result<(), Error> {
+        let (crates, instructions) =
+            parse_initial_state_and_instructions("[A] [B]\n 1   2 \n\nmove 2 from 1 to 2\n")?;
+
+        let Err(AnimationError::Rearrange(error)) = animate(
+            &crates,
+            &instructions,
+            &Crane::CRATE_MOVER_9000,
+            &Animation::default(),
+            &mut Vec::new(),
+        ) else {
+            panic!("Expected a rearrange error");
+        };
+        assert_eq!(error.instruction_number, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_initial_state_and_instructions_handles_wide_drawings() -> Result<(), Error> {
+        let input = "\
+[AB]                                                     [K] 
+[CD] [E]                                                 [L] 
+ 1    2    3    4    5    6    7    8    9    10   11   12 
+
+move 2 from 1 to 12
+";
+
+        let (mut crates, instructions) = parse_initial_state_and_instructions(input)?;
+
+        assert_eq!(crates.stacks.len(), 12);
+        assert_eq!(crates.stacks[0], vec!["CD", "AB"]);
+        assert_eq!(crates.stacks[1], vec!["E"]);
+        assert_eq!(crates.stacks[11], vec!["L", "K"]);
+
+        crates
+            .try_rearrange(&instructions, &Crane::CRATE_MOVER_9001)
+            .map_err(|e| Error::unsolvable(5, e))?;
+        assert_eq!(crates.get_message(), "-E---------AB");
+        assert_eq!(
+            crates.to_drawing(),
+            "                                                       [AB]
+                                                       [CD]
+                                                       [K] 
+     [E]                                               [L] 
+ 1    2    3    4    5    6    7    8    9    10   11   12 "
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_initial_state_and_instructions_reports_crates_without_a_stack() {
+        let error = parse_initial_state_and_instructions("[A] [B] [C]\n 1   2\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "day 5, line 1: crate isn't above a stack number: \"[A] [B] [C]\""
+        );
+    }
+
+    #[test]
+    fn parse_initial_state_and_instructions_reports_crates_above_several_stacks() {
+        let error = parse_initial_state_and_instructions("[ABC]\n 1 2\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "day 5, line 1: crate is above more than one stack number: \"[ABC]\""
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn crates_survive_a_round_trip_through_a_drawing(
+            stacks in prop::collection::vec(prop::collection::vec("[A-Z]{1,3}", 0..8), 1..=12)
+        ) {
+            let crates = Crates { stacks };
+            let drawing = crates.to_drawing();
+
+            let (parsed_crates, instructions) = parse_initial_state_and_instructions(&drawing)?;
+
+            prop_assert_eq!(&parsed_crates, &crates);
+            prop_assert_eq!(parsed_crates.to_drawing(), drawing);
+            prop_assert!(instructions.is_empty());
+        }
+    }
+
+    #[test]
+    fn parse_initial_state_and_instructions_reports_malformed_instructions() {
+        let input = "
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move one from 1 to 3
+";
+        let error = parse_initial_state_and_instructions(input).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "day 5, line 6: expected a number: \"move one from 1 to 3\""
+        );
+    }
+
+    #[test]
+    fn get_message_from_rearranged_crates_moving_multiple_crates_at_once_returns_the_correct_answer(
+    ) {
+        let input = "
+    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+
+";
+        let answer = get_message_from_rearranged_crates(input, &Crane::CRATE_MOVER_9001).unwrap();
+
         assert_eq!(answer, "MCD");
     }
 }
 
 pub mod part1 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day5.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(5)?;
 
-        let answer = get_message_from_rearranged_crates(
-            &input,
-            &Settings {
-                move_multiple_crates_at_once: false,
-            },
-        );
+        let answer = Day5.part1(&input)?;
 
-        println!("day 5 part 1 message = {answer:?}");
+        println!("day 5 part 1 message = {answer}");
+
+        Ok(())
     }
 }
 
 pub mod part2 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day5.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(5)?;
 
-        let answer = get_message_from_rearranged_crates(
-            &input,
-            &Settings {
-                move_multiple_crates_at_once: true,
-            },
-        );
+        let answer = Day5.part2(&input)?;
+
+        println!("day 5 part 2 message = {answer}");
+
+        Ok(())
+    }
+}
+
+/// Supply Stacks.
+pub struct Day5;
+
+impl Puzzle for Day5 {
+    type Input<'a> = (Crates, Vec<Instruction>);
+
+    fn parse(&self, input: &str) -> Result<(Crates, Vec<Instruction>), Error> {
+        parse_initial_state_and_instructions(input)
+    }
+
+    fn solve_part1(&self, (crates, instructions): &Self::Input<'_>) -> Result<Answer, Error> {
+        let mut crates = crates.clone();
+        crates
+            .try_rearrange(instructions, &Crane::CRATE_MOVER_9000)
+            .map_err(|e| Error::unsolvable(5, e))?;
+
+        Ok(crates.get_message().into())
+    }
+
+    fn solve_part2(&self, (crates, instructions): &Self::Input<'_>) -> Result<Answer, Error> {
+        let mut crates = crates.clone();
+        crates
+            .try_rearrange(instructions, &Crane::CRATE_MOVER_9001)
+            .map_err(|e| Error::unsolvable(5, e))?;
+
+        Ok(crates.get_message().into())
+    }
+}
+
+/// How a crane moves crates from one stack to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Crane {
+    /// Moves crates one at a time, reversing their order.
+    OneAtATime,
+
+    /// Moves all the crates in one lift, keeping their order.
+    AllAtOnce,
+
+    /// Moves up to a fixed number of crates in each lift, keeping the order within a lift.
+    LiftCapacity(NonZeroUsize),
+
+    /// Moves all the crates in one lift, keeping their order, but can't stack crates any
+    /// higher than the limit.
+    HeightLimited { max_height: usize },
+}
+
+/// How much using a crane costs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostModel {
+    pub per_lift: u64,
+    pub per_crate: u64,
+}
+
+/// What a crane did while rearranging crates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CraneStats {
+    pub num_instructions: usize,
+    pub num_lifts: usize,
+    pub num_crates_moved: usize,
+}
+
+impl Crane {
+    /// The CrateMover 9000 from part 1.
+    pub const CRATE_MOVER_9000: Crane = Crane::OneAtATime;
 
-        println!("day 5 part 2 message = {answer:?}");
+    /// The CrateMover 9001 from part 2.
+    pub const CRATE_MOVER_9001: Crane = Crane::AllAtOnce;
+
+    /// A crane that lifts up to `capacity` crates at a time, or `None` if it can't lift any.
+    pub fn with_lift_capacity(capacity: usize) -> Option<Self> {
+        NonZeroUsize::new(capacity).map(Crane::LiftCapacity)
+    }
+
+    /// A crane that keeps the order of the crates, but can't build stacks over `max_height`.
+    pub fn with_height_limit(max_height: usize) -> Self {
+        Crane::HeightLimited { max_height }
+    }
+
+    /// The number of lifts it takes to move `quantity` crates.
+    pub fn num_lifts(&self, quantity: usize) -> usize {
+        quantity.div_ceil(self.lift_size(quantity))
+    }
+
+    /// The most crates moved in each lift, when moving `quantity` crates.
+    fn lift_size(&self, quantity: usize) -> usize {
+        match self {
+            Crane::OneAtATime => 1,
+            Crane::AllAtOnce | Crane::HeightLimited { .. } => quantity.max(1),
+            Crane::LiftCapacity(capacity) => capacity.get(),
+        }
     }
 }
 
-pub struct Settings {
-    move_multiple_crates_at_once: bool,
+impl CraneStats {
+    /// The total cost of the lifts and crates moved.
+    pub fn cost(&self, cost_model: &CostModel) -> u64 {
+        self.num_lifts as u64 * cost_model.per_lift
+            + self.num_crates_moved as u64 * cost_model.per_crate
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Crates {
     pub stacks: Vec<Vec<String>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Instruction {
     Move {
         quantity: usize,
@@ -107,90 +474,471 @@ pub enum Instruction {
     },
 }
 
+impl Display for Instruction {
+    /// Prints the instruction as it appears in the puzzle input, with stacks numbered from 1.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Move {
+                quantity,
+                from_stack,
+                to_stack,
+            } => write!(
+                f,
+                "move {quantity} from {} to {}",
+                from_stack + 1,
+                to_stack + 1
+            ),
+        }
+    }
+}
+
+/// Why a crane could not carry out an instruction. Stacks are numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum MoveError {
+    #[error("there is no stack {stack_number}")]
+    UnknownStack { stack_number: usize },
+
+    #[error("stack {stack_number} only has {num_crates} crate(s), but {quantity} need moving")]
+    NotEnoughCrates {
+        stack_number: usize,
+        num_crates: usize,
+        quantity: usize,
+    },
+
+    #[error("stack {stack_number} can't be over {max_height} crates")]
+    StackTooTall {
+        stack_number: usize,
+        max_height: usize,
+    },
+}
+
+/// An instruction that could not be carried out while rearranging crates.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("instruction {instruction_number} ({instruction}): {reason}")]
+pub struct RearrangeError {
+    /// The number of the instruction (starting at 1).
+    pub instruction_number: usize,
+
+    pub instruction: Instruction,
+
+    pub reason: MoveError,
+}
+
+/// What [`Crates::get_message`] shows for an empty stack.
+pub const EMPTY_STACK: &str = "-";
+
 impl Crates {
+    /// Draws the stacks the way the puzzle input does, with the stack numbers underneath.
+    pub fn to_drawing(&self) -> String {
+        self.to_string()
+    }
+
+    /// The crates on top of each stack, with [`EMPTY_STACK`] for stacks without any.
     pub fn get_message(&self) -> String {
         self.stacks
             .iter()
-            .map(|s| s.last().unwrap())
+            .map(|s| s.last().map_or(EMPTY_STACK, String::as_str))
             .fold(String::new(), |mut message, s| {
                 message.push_str(s);
                 message
             })
     }
 
-    pub fn rearrange(&mut self, instructions: &[Instruction], settings: &Settings) {
-        for instruction in instructions {
-            match instruction {
-                Instruction::Move {
-                    quantity,
-                    from_stack,
-                    to_stack,
-                } => {
-                    let source = self.stacks.get_mut(*from_stack).unwrap();
-
-                    let mut payload = source
-                        .splice(source.len() - *quantity..source.len(), [])
-                        .collect::<Vec<String>>();
-                    if !settings.move_multiple_crates_at_once {
-                        payload.reverse();
+    /// Carries out every instruction with the given crane, returning what the crane did, or the
+    /// first instruction that can't be carried out.
+    ///
+    /// The instructions before the failed one are still carried out, but the failed one doesn't
+    /// move any crates.
+    pub fn try_rearrange(
+        &mut self,
+        instructions: &[Instruction],
+        crane: &Crane,
+    ) -> Result<CraneStats, RearrangeError> {
+        let mut stats = CraneStats::default();
+
+        for (instruction_number, instruction) in (1..).zip(instructions) {
+            let Instruction::Move { quantity, .. } = instruction;
+
+            self.apply(instruction, crane)
+                .map_err(|reason| RearrangeError {
+                    instruction_number,
+                    instruction: instruction.clone(),
+                    reason,
+                })?;
+            stats.num_instructions += 1;
+            stats.num_lifts += crane.num_lifts(*quantity);
+            stats.num_crates_moved += quantity;
+        }
+
+        Ok(stats)
+    }
+
+    /// Rearranges the crates one instruction at a time, yielding the crates after each one.
+    pub fn steps<'a>(&self, instructions: &'a [Instruction], crane: &'a Crane) -> Steps<'a> {
+        Steps {
+            crates: self.clone(),
+            instructions: instructions.iter(),
+            crane,
+            instruction_number: 0,
+        }
+    }
+
+    /// Draws the stacks like [`Crates::to_drawing`], highlighting the crates at the given
+    /// positions in bold yellow.
+    pub fn to_highlighted_drawing(&self, highlighted: &[CratePosition]) -> String {
+        let mut drawing = String::new();
+        // Writing to a string never fails.
+        let _ = self.draw(&mut drawing, highlighted);
+        drawing
+    }
+
+    /// Carries out a single instruction, returning where the moved crates ended up. Nothing
+    /// moves if the instruction can't be carried out.
+    fn apply(
+        &mut self,
+        instruction: &Instruction,
+        crane: &Crane,
+    ) -> Result<Vec<CratePosition>, MoveError> {
+        match instruction {
+            Instruction::Move {
+                quantity,
+                from_stack,
+                to_stack,
+            } => {
+                let stack_len = |stack: usize| {
+                    self.stacks
+                        .get(stack)
+                        .map(Vec::len)
+                        .ok_or(MoveError::UnknownStack {
+                            stack_number: stack + 1,
+                        })
+                };
+
+                let num_crates = stack_len(*from_stack)?;
+                if num_crates < *quantity {
+                    return Err(MoveError::NotEnoughCrates {
+                        stack_number: from_stack + 1,
+                        num_crates,
+                        quantity: *quantity,
+                    });
+                }
+
+                // Moving crates onto the same stack leaves it as high as it was.
+                let height = stack_len(*to_stack)? + quantity
+                    - if from_stack == to_stack { *quantity } else { 0 };
+                if let Crane::HeightLimited { max_height } = crane {
+                    if height > *max_height {
+                        return Err(MoveError::StackTooTall {
+                            stack_number: to_stack + 1,
+                            max_height: *max_height,
+                        });
                     }
+                }
+
+                let source = &mut self.stacks[*from_stack];
+                let payload = source.split_off(num_crates - quantity);
 
-                    let destination = self.stacks.get_mut(*to_stack).unwrap();
-                    destination.append(&mut payload);
+                // Each lift takes the crates from the top of what is left to move.
+                let destination = &mut self.stacks[*to_stack];
+                for lift in payload.rchunks(crane.lift_size(*quantity)) {
+                    destination.extend_from_slice(lift);
                 }
+
+                Ok((destination.len() - *quantity..destination.len())
+                    .map(|level| CratePosition {
+                        stack: *to_stack,
+                        level,
+                    })
+                    .collect())
             }
         }
     }
+
+    /// Draws the stacks, wrapping the highlighted crates in ANSI escape codes.
+    fn draw(
+        &self,
+        f: &mut impl std::fmt::Write,
+        highlighted: &[CratePosition],
+    ) -> std::fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+
+        // Every stack is as wide as the widest crate or stack number, so that the columns line
+        // up. Crates and stack numbers are centred in their stack, so they always overlap.
+        let widest_crate = self
+            .stacks
+            .iter()
+            .flatten()
+            .map(|label| label.chars().count() + 2)
+            .max()
+            .unwrap_or(3);
+        let width = widest_crate.max(self.stacks.len().to_string().len());
+
+        for level in (0..height).rev() {
+            let row = self
+                .stacks
+                .iter()
+                .enumerate()
+                .map(|(stack, crates)| {
+                    let Some(label) = crates.get(level) else {
+                        return " ".repeat(width);
+                    };
+
+                    let padding = width - label.chars().count() - 2;
+                    let (left, right) =
+                        (" ".repeat(padding / 2), " ".repeat(padding - padding / 2));
+                    if highlighted.contains(&CratePosition { stack, level }) {
+                        format!("{left}{HIGHLIGHT}[{label}]{RESET}{right}")
+                    } else {
+                        format!("{left}[{label}]{right}")
+                    }
+                })
+                .collect::<Vec<String>>();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+
+        let footer = (1..=self.stacks.len())
+            .map(|stack_number| format!("{stack_number:^width$}"))
+            .collect::<Vec<String>>();
+        write!(f, "{}", footer.join(" "))
+    }
+}
+
+/// ANSI escape codes for highlighting moved crates.
+const HIGHLIGHT: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// ANSI escape code that clears the terminal and moves the cursor to the top left.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Where a crate is: its stack (starting at 0), and how far up the stack it is (starting at 0
+/// for the bottom crate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CratePosition {
+    pub stack: usize,
+    pub level: usize,
+}
+
+/// The crates after carrying out an instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The number of the instruction (starting at 1) that was carried out.
+    pub instruction_number: usize,
+
+    pub crates: Crates,
+
+    /// Where the crates moved by the instruction ended up.
+    pub moved: Vec<CratePosition>,
+}
+
+/// An iterator over the crates after each instruction, created by [`Crates::steps`]. Stops
+/// after the first instruction that can't be carried out.
+pub struct Steps<'a> {
+    crates: Crates,
+    instructions: std::slice::Iter<'a, Instruction>,
+    crane: &'a Crane,
+    instruction_number: usize,
+}
+
+impl Iterator for Steps<'_> {
+    type Item = Result<Snapshot, RearrangeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = self.instructions.next()?;
+        self.instruction_number += 1;
+
+        match self.crates.apply(instruction, self.crane) {
+            Ok(moved) => Some(Ok(Snapshot {
+                instruction_number: self.instruction_number,
+                crates: self.crates.clone(),
+                moved,
+            })),
+            Err(reason) => {
+                self.instructions = [].iter();
+                Some(Err(RearrangeError {
+                    instruction_number: self.instruction_number,
+                    instruction: instruction.clone(),
+                    reason,
+                }))
+            }
+        }
+    }
+}
+
+/// How to animate a rearrangement in the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Animation {
+    /// How long to show the crates after each instruction.
+    pub delay: Duration,
+
+    /// Whether to highlight the crates moved by each instruction.
+    pub highlight: bool,
+}
+
+/// Why an animation stopped early.
+#[derive(Debug, thiserror::Error)]
+pub enum AnimationError {
+    /// The crates couldn't be drawn.
+    #[error("unable to draw the crates: {0}")]
+    Io(#[from] io::Error),
+
+    /// An instruction couldn't be carried out.
+    #[error(transparent)]
+    Rearrange(#[from] RearrangeError),
+}
+
+/// Redraws the stacks after each instruction, starting with the initial drawing.
+///
+/// # Errors
+///
+/// Fails if writing fails, or if an instruction can't be carried out. The moves before the
+/// failed instruction have already been drawn.
+pub fn animate(
+    crates: &Crates,
+    instructions: &[Instruction],
+    crane: &Crane,
+    animation: &Animation,
+    out: &mut impl Write,
+) -> Result<(), AnimationError> {
+    writeln!(out, "{CLEAR_SCREEN}{}", crates.to_drawing())?;
+    out.flush()?;
+
+    for snapshot in crates.steps(instructions, crane) {
+        let snapshot = snapshot?;
+        thread::sleep(animation.delay);
+
+        let highlighted = if animation.highlight {
+            &snapshot.moved[..]
+        } else {
+            &[]
+        };
+        writeln!(
+            out,
+            "{CLEAR_SCREEN}{}\nmove {} of {}",
+            snapshot.crates.to_highlighted_drawing(highlighted),
+            snapshot.instruction_number,
+            instructions.len()
+        )?;
+        out.flush()?;
+    }
+
+    Ok(())
+}
+
+impl Display for Crates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.draw(f, &[])
+    }
 }
 
 enum ParserState {
-    InitialState { has_parsed_data: bool },
+    InitialState,
     Instructions,
 }
 
+/// Finds the columns (counted in characters) spanned by each stack number in the line under
+/// the drawing. The stacks must be numbered from 1, in order.
+fn parse_stack_columns(line: &str) -> Result<Vec<RangeInclusive<usize>>, &'static str> {
+    let mut columns = Vec::<RangeInclusive<usize>>::new();
+    let mut stack_number = String::new();
+
+    // A trailing space ends the last stack number.
+    for (column, c) in line.chars().chain([' ']).enumerate() {
+        if c.is_ascii_digit() {
+            stack_number.push(c);
+            continue;
+        }
+        if !c.is_whitespace() {
+            return Err("expected stack numbers under the drawing");
+        }
+        if stack_number.is_empty() {
+            continue;
+        }
+
+        if stack_number.parse() != Ok(columns.len() + 1) {
+            return Err("stacks must be numbered from 1, in order");
+        }
+        columns.push(column - stack_number.len()..=column - 1);
+        stack_number.clear();
+    }
+
+    Ok(columns)
+}
+
+/// Finds the crates drawn on a line as `[X]`, along with the stack each is above.
+fn parse_crate_row(
+    line: &str,
+    columns: &[RangeInclusive<usize>],
+) -> Result<Vec<(usize, String)>, &'static str> {
+    let chars = line.chars().collect::<Vec<char>>();
+    let mut crates = Vec::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        match chars[start] {
+            c if c.is_whitespace() => start += 1,
+            '[' => {
+                let end = (start + 1..chars.len())
+                    .find(|&end| chars[end] == ']')
+                    .ok_or("crates must be drawn as `[X]`")?;
+                if end == start + 1 {
+                    return Err("crates must have a label");
+                }
+
+                let mut stacks = columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, column)| *column.start() <= end && start <= *column.end())
+                    .map(|(stack, _)| stack);
+                let stack = stacks.next().ok_or("crate isn't above a stack number")?;
+                if stacks.next().is_some() {
+                    return Err("crate is above more than one stack number");
+                }
+                crates.push((stack, chars[start + 1..end].iter().collect()));
+
+                start = end + 1;
+            }
+            _ => return Err("crates must be drawn as `[X]`"),
+        }
+    }
+
+    Ok(crates)
+}
+
 pub fn parse_initial_state_and_instructions(
     initial_state_and_instructions: &str,
-) -> (Crates, Vec<Instruction>) {
+) -> Result<(Crates, Vec<Instruction>), Error> {
     let mut stacks = Vec::<Vec<String>>::new();
     let mut instructions = Vec::<Instruction>::new();
-    let mut parser_state = ParserState::InitialState {
-        has_parsed_data: false,
-    };
+    let mut parser_state = ParserState::InitialState;
 
-    for line in initial_state_and_instructions.lines() {
-        match parser_state {
-            ParserState::InitialState { has_parsed_data } => {
-                if has_parsed_data && !line.contains('[') {
-                    // This is the line with the stack numbers... switch to parsing instructions...
-                    parser_state = ParserState::Instructions;
-                    continue;
-                }
+    // The stack numbers come after the drawing, so hold on to its rows until they turn up.
+    let mut drawing = Vec::<(usize, &str)>::new();
 
-                // Each crate takes up three characters, followed by an optional space...
-                // Conceptually, forcing each crate to have a trailing space and grabbing every
-                // 4 characters will gather the crates present on this line.
-                let num_crates = (line.trim_end().len() + 1) / 4;
+    for (line_number, line) in (1..).zip(initial_state_and_instructions.lines()) {
+        let parse_error = |reason| Error::parse(5, line_number, line, reason);
 
-                // Always ensure we have the correct number of stacks.
-                while stacks.len() < num_crates {
-                    stacks.push(Vec::<String>::new());
+        match parser_state {
+            ParserState::InitialState => {
+                if !line.trim_start().starts_with(|c: char| c.is_ascii_digit()) {
+                    drawing.push((line_number, line));
+                    continue;
                 }
 
-                // Grab each crate and put it on the BOTTOM of its stack.
-                for i in 0..num_crates {
-                    // Every 4 characters is a crate, but we really only need the letter from it,
-                    // e.g. `[N] ` yields `N`...
-                    let starting_offset = i * 4;
-                    let c = &line[starting_offset + 1..starting_offset + 2];
-                    if c.trim().is_empty() {
-                        continue;
+                // This is the line with the stack numbers, which says where each stack is...
+                // switch to parsing instructions...
+                let columns = parse_stack_columns(line).map_err(parse_error)?;
+                stacks = vec![Vec::new(); columns.len()];
+
+                // Build the stacks from the bottom up.
+                for &(line_number, line) in drawing.iter().rev() {
+                    let crates = parse_crate_row(line, &columns)
+                        .map_err(|reason| Error::parse(5, line_number, line, reason))?;
+                    for (stack, label) in crates {
+                        stacks[stack].push(label);
                     }
-                    stacks.get_mut(i).unwrap().insert(0, c.to_owned());
                 }
 
-                parser_state = ParserState::InitialState {
-                    has_parsed_data: true,
-                };
+                parser_state = ParserState::Instructions;
             }
             ParserState::Instructions => {
                 if line.trim().is_empty() {
@@ -198,9 +946,23 @@ pub fn parse_initial_state_and_instructions(
                 }
 
                 let parts: Vec<&str> = line.split(' ').collect();
-                let quantity = parts.get(1).unwrap().parse::<usize>().unwrap();
-                let from_stack = parts.get(3).unwrap().parse::<usize>().unwrap() - 1;
-                let to_stack = parts.get(5).unwrap().parse::<usize>().unwrap() - 1;
+                let ["move", quantity, "from", from_stack, "to", to_stack] = parts[..] else {
+                    return Err(parse_error("expected `move <n> from <stack> to <stack>`"));
+                };
+
+                let parse_number = |s: &str| {
+                    s.parse::<usize>()
+                        .map_err(|_| parse_error("expected a number"))
+                };
+                let parse_stack_number = |s: &str| {
+                    parse_number(s)?
+                        .checked_sub(1)
+                        .ok_or_else(|| parse_error("stack numbers start at 1"))
+                };
+
+                let quantity = parse_number(quantity)?;
+                let from_stack = parse_stack_number(from_stack)?;
+                let to_stack = parse_stack_number(to_stack)?;
                 instructions.push(Instruction::Move {
                     quantity,
                     from_stack,
@@ -210,18 +972,32 @@ pub fn parse_initial_state_and_instructions(
         }
     }
 
+    if let ParserState::InitialState = parser_state {
+        if let Some(&(line_number, line)) = drawing.iter().find(|(_, line)| !line.trim().is_empty())
+        {
+            return Err(Error::parse(
+                5,
+                line_number,
+                line,
+                "expected stack numbers under the drawing",
+            ));
+        }
+    }
+
     let crates = Crates { stacks };
-    (crates, instructions)
+    Ok((crates, instructions))
 }
 
 pub fn get_message_from_rearranged_crates(
     initial_state_and_instructions: &str,
-    settings: &Settings,
-) -> String {
+    crane: &Crane,
+) -> Result<String, Error> {
     let (mut crates, instructions) =
-        parse_initial_state_and_instructions(initial_state_and_instructions);
+        parse_initial_state_and_instructions(initial_state_and_instructions)?;
 
-    crates.rearrange(&instructions, settings);
+    crates
+        .try_rearrange(&instructions, crane)
+        .map_err(|e| Error::unsolvable(5, e))?;
 
-    crates.get_message()
+    Ok(crates.get_message())
 }
@@ -1,6 +1,12 @@
 use std::collections::{HashSet, VecDeque};
 use std::io::Read;
 
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,112 +16,213 @@ mod tests {
         let device = HandheldDevice::new();
 
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes(),
-                4
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes(),
+                    4
+                )
+                .unwrap(),
             7
         );
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "bvwbjplbgvbhsrlpgdmjqwftvncz".as_bytes(),
-                4
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "bvwbjplbgvbhsrlpgdmjqwftvncz".as_bytes(),
+                    4
+                )
+                .unwrap(),
             5
         );
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "nppdvjthqldpwncqszvftbrmjlhg".as_bytes(),
-                4
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "nppdvjthqldpwncqszvftbrmjlhg".as_bytes(),
+                    4
+                )
+                .unwrap(),
             6
         );
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".as_bytes(),
-                4
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".as_bytes(),
+                    4
+                )
+                .unwrap(),
             10
         );
 
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".as_bytes(),
-                4
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".as_bytes(),
+                    4
+                )
+                .unwrap(),
             11
         );
 
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes(),
-                14
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes(),
+                    14
+                )
+                .unwrap(),
             19
         );
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "bvwbjplbgvbhsrlpgdmjqwftvncz".as_bytes(),
-                14
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "bvwbjplbgvbhsrlpgdmjqwftvncz".as_bytes(),
+                    14
+                )
+                .unwrap(),
             23
         );
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "nppdvjthqldpwncqszvftbrmjlhg".as_bytes(),
-                14
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "nppdvjthqldpwncqszvftbrmjlhg".as_bytes(),
+                    14
+                )
+                .unwrap(),
             23
         );
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".as_bytes(),
-                14
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".as_bytes(),
+                    14
+                )
+                .unwrap(),
             29
         );
 
         assert_eq!(
-            device.get_num_characters_processed_for_start_marker_detection(
-                "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".as_bytes(),
-                14
-            ),
+            device
+                .get_num_characters_processed_for_start_marker_detection(
+                    "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".as_bytes(),
+                    14
+                )
+                .unwrap(),
             26
         );
     }
+
+    #[test]
+    fn get_num_characters_processed_for_start_marker_detection_reports_a_missing_marker() {
+        let device = HandheldDevice::new();
+
+        let error = device
+            .get_num_characters_processed_for_start_marker_detection("abab".as_bytes(), 4)
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "day 6: no start marker in the first 4 characters"
+        );
+    }
+
+    #[test]
+    fn parse_reports_datastreams_that_are_not_a_single_line_of_letters() {
+        let Err(error) = Day6.parse("abcd\nefgh\n") else {
+            panic!("Expected an error");
+        };
+        assert_eq!(
+            error.to_string(),
+            "day 6, line 2: expected the datastream on a single line: \"efgh\""
+        );
+
+        let Err(error) = Day6.parse("ab cd\n") else {
+            panic!("Expected an error");
+        };
+        assert_eq!(
+            error.to_string(),
+            "day 6, line 1: the datastream must only contain lowercase letters: \"ab cd\""
+        );
+    }
 }
 
 pub mod part1 {
-    use std::{env::current_dir, fs::read_to_string};
-
     use super::*;
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day6.txt");
-        let input = read_to_string(filename).unwrap();
-        let device = HandheldDevice::new();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(6)?;
 
-        let answer =
-            device.get_num_characters_processed_for_start_marker_detection(input.as_bytes(), 4);
+        let answer = Day6.part1(&input)?;
+
+        println!("day 6 part 1 answer = {answer}");
 
-        println!("day 6 part 1 answer = {answer:#?}");
+        Ok(())
     }
 }
 
 pub mod part2 {
-    use std::{env::current_dir, fs::read_to_string};
-
     use super::*;
 
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day6.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(6)?;
+
+        let answer = Day6.part2(&input)?;
+
+        println!("day 6 part 2 answer = {answer}");
+
+        Ok(())
+    }
+}
+
+/// Tuning Trouble.
+pub struct Day6;
+
+impl Puzzle for Day6 {
+    type Input<'a> = &'a [u8];
+
+    /// Checks that the datastream is a single line of lowercase letters.
+    fn parse<'a>(&self, input: &'a str) -> Result<&'a [u8], Error> {
+        let mut lines = (1..)
+            .zip(input.lines())
+            .skip_while(|(_, line)| line.is_empty());
+        let Some((line_number, datastream)) = lines.next() else {
+            return Ok(&[]);
+        };
+
+        if let Some((line_number, line)) = lines.find(|(_, line)| !line.is_empty()) {
+            return Err(Error::parse(
+                6,
+                line_number,
+                line,
+                "expected the datastream on a single line",
+            ));
+        }
+        if !datastream.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Err(Error::parse(
+                6,
+                line_number,
+                datastream,
+                "the datastream must only contain lowercase letters",
+            ));
+        }
+
+        Ok(datastream.as_bytes())
+    }
+
+    fn solve_part1(&self, datastream: &&[u8]) -> Result<Answer, Error> {
+        let device = HandheldDevice::new();
+
+        let answer =
+            device.get_num_characters_processed_for_start_marker_detection(*datastream, 4)?;
+
+        Ok(answer.into())
+    }
+
+    fn solve_part2(&self, datastream: &&[u8]) -> Result<Answer, Error> {
         let device = HandheldDevice::new();
 
         let answer =
-            device.get_num_characters_processed_for_start_marker_detection(input.as_bytes(), 14);
+            device.get_num_characters_processed_for_start_marker_detection(*datastream, 14)?;
 
-        println!("day 6 part 2 answer = {answer:#?}");
+        Ok(answer.into())
     }
 }
 
@@ -137,14 +244,26 @@ impl HandheldDevice {
         &self,
         mut datastream: impl Read,
         num_unique_bytes: usize,
-    ) -> usize {
+    ) -> Result<usize, Error> {
         let mut bytes_read = 0usize;
         let mut current_sequence = VecDeque::<u8>::new();
 
         loop {
             let mut buffer: [u8; 1] = [0];
-            if let Err(e) = datastream.read(&mut buffer) {
-                panic!("{}", e);
+            match datastream.read(&mut buffer) {
+                Ok(0) => {
+                    return Err(Error::unsolvable(
+                        6,
+                        format!("no start marker in the first {bytes_read} characters"),
+                    ))
+                }
+                Ok(_) => (),
+                Err(e) => {
+                    return Err(Error::unsolvable(
+                        6,
+                        format!("unable to read the datastream: {e}"),
+                    ))
+                }
             }
             bytes_read += 1;
 
@@ -157,7 +276,7 @@ impl HandheldDevice {
             let byte_set: HashSet<u8> = current_sequence.clone().into_iter().collect();
 
             if bytes_read > num_unique_bytes && byte_set.len() == num_unique_bytes {
-                return bytes_read;
+                return Ok(bytes_read);
             }
         }
     }
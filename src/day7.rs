@@ -3,6 +3,12 @@
 
 use std::{cell::RefCell, collections::HashMap};
 
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,6 +51,23 @@ $ ls
         Ok(())
     }
 
+    #[test]
+    fn from_terminal_replay_reports_unrecognized_output() {
+        let terminal_replay = "$ cd /
+$ ls
+12ab b.txt
+";
+
+        let Err(error) = FileSystem::from_terminal_replay(terminal_replay) else {
+            panic!("Expected an error");
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "day 7, line 3: invalid file size: \"12ab b.txt\""
+        );
+    }
+
     #[test]
     fn get_size_of_smallest_directory_leaving_space_returns_the_correct_answer() {
         let terminal_replay = "
@@ -84,36 +107,59 @@ $ ls
 /// Part 1.
 pub mod part1 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
     /// The solution for Part 1.
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day7.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(7)?;
 
-        let file_system = FileSystem::from_terminal_replay(&input).unwrap();
-        let answer = file_system.get_total_size(Criteria {
-            size_range: (0, 100000),
-        });
+        let answer = Day7.part1(&input)?;
 
         println!("Part 1 Solution = {}", answer);
+
+        Ok(())
     }
 }
 
 /// Part 2
 pub mod part2 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
     /// The solution for Part 2.
-    pub fn solution() {
-        let filename = current_dir().unwrap().join("src/data/day7.txt");
-        let input = read_to_string(filename).unwrap();
+    pub fn solution() -> Result<(), Error> {
+        let input = InputSource::default().read(7)?;
 
-        let file_system = FileSystem::from_terminal_replay(&input).unwrap();
-        let answer = file_system.get_size_of_smallest_directory_leaving_space(70000000, 30000000);
+        let answer = Day7.part2(&input)?;
+
+        println!("Part 2 Solution = {}", answer);
+
+        Ok(())
+    }
+}
+
+/// No Space Left On Device.
+pub struct Day7;
+
+impl Puzzle for Day7 {
+    type Input<'a> = FileSystem;
+
+    fn parse(&self, input: &str) -> Result<FileSystem, Error> {
+        FileSystem::from_terminal_replay(input)
+    }
+
+    fn solve_part1(&self, file_system: &FileSystem) -> Result<Answer, Error> {
+        let answer = file_system.get_total_size(Criteria {
+            size_range: (0, 100000),
+        });
 
-        println!("Part 2 Solution = {:?}", answer);
+        Ok(answer.into())
+    }
+
+    fn solve_part2(&self, file_system: &FileSystem) -> Result<Answer, Error> {
+        let answer = file_system
+            .get_size_of_smallest_directory_leaving_space(70000000, 30000000)
+            .ok_or_else(|| Error::unsolvable(7, "no directory frees up enough space"))?;
+
+        Ok(answer.into())
     }
 }
 
@@ -187,46 +233,6 @@ pub struct Criteria {
     size_range: (u64, u64),
 }
 
-/// An error in the program.
-#[derive(Debug)]
-pub struct Error {
-    /// The kind of error.
-    #[allow(dead_code)]
-    kind: ErrorKind,
-}
-
-/// The different kinds of errors in our program.
-#[derive(Debug)]
-pub enum ErrorKind {
-    /// A generic error with an optional explanation.
-    Generic(Option<String>),
-
-    /// Terminal parse errors.
-    TerminalParseError {
-        /// The kind of terminal parse error.
-        kind: TerminalParseErrorKind,
-
-        /// The offending line.
-        line: String,
-
-        /// The line number it occurred on (relative to parsing).
-        parsed_line_number: usize,
-    },
-}
-
-/// Terminal parse errors.
-#[derive(Debug)]
-pub enum TerminalParseErrorKind {
-    /// A generic error.
-    Generic,
-
-    /// Invalid file size error.
-    InvalidFileSize,
-
-    /// Invalid file name error.
-    InvalidFileName,
-}
-
 impl FileSystem {
     /// Finds the smallest file that, if deleted, will satisfy the space needed, and returns
     /// its size.
@@ -238,8 +244,7 @@ impl FileSystem {
         let space_used = *self
             .directory_index
             .borrow()
-            .get("/")
-            .unwrap()
+            .get("/")?
             .borrow()
             .size
             .borrow();
@@ -248,13 +253,15 @@ impl FileSystem {
             .directory_index
             .borrow()
             .values()
-            .into_iter()
             .map(|rc| *rc.borrow().size.borrow())
             .collect::<Vec<u64>>();
         sorted_directory_sizes.sort();
 
         for directory_size in sorted_directory_sizes {
-            let free_space_if_deleted = capacity - (space_used - directory_size);
+            let Some(free_space_if_deleted) = capacity.checked_sub(space_used - directory_size)
+            else {
+                continue;
+            };
             if free_space_if_deleted >= space_needed {
                 return Some(directory_size);
             }
@@ -282,7 +289,7 @@ impl FileSystem {
             directory_index: RefCell::new(directory_index),
         };
 
-        file_system.fill_from_replay(&terminal_events).unwrap();
+        file_system.fill_from_replay(&terminal_events)?;
 
         Ok(file_system)
     }
@@ -306,9 +313,15 @@ impl FileSystem {
     }
 
     /// Processes terminal events into the file system entry.
-    fn fill_from_replay(&mut self, terminal_events: &[TerminalEvent]) -> Result<(), &'static str> {
+    fn fill_from_replay(&mut self, terminal_events: &[TerminalEvent]) -> Result<(), Error> {
         let mut stack = vec!["/".to_string()];
         let root_path = "/".to_string();
+        let missing_directory = |path: &str| {
+            Error::unsolvable(
+                7,
+                format!("directory {path} is missing from the file system"),
+            )
+        };
 
         for event in terminal_events.iter() {
             match event {
@@ -316,7 +329,10 @@ impl FileSystem {
                     if target_directory == "/" {
                         stack.splice(1.., vec![]);
                     } else if target_directory == ".." {
-                        stack.pop();
+                        // The root directory is its own parent.
+                        if stack.len() > 1 {
+                            stack.pop();
+                        }
                     } else {
                         let current_path = stack.last().unwrap_or(&root_path);
                         let directory_key = format!("{}/{}", current_path, &target_directory);
@@ -336,7 +352,10 @@ impl FileSystem {
                     let last_index = stack.len() - 1;
                     for (index, current_path) in stack.iter().enumerate() {
                         let di = self.directory_index.borrow();
-                        let current_directory = di.get(current_path.as_str()).unwrap().borrow();
+                        let current_directory = di
+                            .get(current_path.as_str())
+                            .ok_or_else(|| missing_directory(current_path))?
+                            .borrow();
                         if index == last_index {
                             *current_directory.local_size.borrow_mut() += size;
                         }
@@ -367,7 +386,9 @@ impl FileSystem {
                         .insert(directory_key.clone(), RefCell::new(new_dir));
 
                     let di = self.directory_index.borrow();
-                    let current_directory = di.get(current_path.as_str()).unwrap();
+                    let current_directory = di
+                        .get(current_path.as_str())
+                        .ok_or_else(|| missing_directory(current_path))?;
                     current_directory
                         .borrow_mut()
                         .sub_directory_keys
@@ -397,11 +418,13 @@ impl FileSystem {
     fn parsed_terminal_events(terminal_replay: &str) -> Result<Vec<TerminalEvent>, Error> {
         let mut terminal_events = vec![];
 
-        for (line_number, line) in terminal_replay.lines().enumerate() {
+        for (line_number, line) in (1..).zip(terminal_replay.lines()) {
             if line.trim().is_empty() {
                 continue;
             }
 
+            let parse_error = |reason| Error::parse(7, line_number, line, reason);
+
             let event = {
                 if let Some(name) = line.strip_prefix("$ cd ") {
                     TerminalEvent::ChangeDirectory(name.to_string())
@@ -409,27 +432,17 @@ impl FileSystem {
                     TerminalEvent::ListDirectoryContents
                 } else if let Some(name) = line.strip_prefix("dir ") {
                     TerminalEvent::Listing(FileSystemListing::Directory(name.to_string()))
-                } else if line[..1].starts_with(|l: char| l.is_ascii_digit()) {
+                } else if line.starts_with(|l: char| l.is_ascii_digit()) {
                     let parts: Vec<&str> = line.split(' ').collect();
-                    let Ok(size) = parts
-                        .first()
-                        .ok_or(Error { kind: ErrorKind::TerminalParseError { kind: TerminalParseErrorKind::Generic, line: line.to_string(), parsed_line_number: line_number }})?
-                        .parse::<u64>() else {
-                            return Err(Error { kind: ErrorKind::TerminalParseError { kind: TerminalParseErrorKind::InvalidFileSize, line: line.to_string(), parsed_line_number: line_number }});
+                    let [size, name] = parts[..] else {
+                        return Err(parse_error("expected a file size and a file name"));
                     };
-                    let name = parts
-                        .get(1)
-                        .ok_or(Error {
-                            kind: ErrorKind::TerminalParseError {
-                                kind: TerminalParseErrorKind::InvalidFileSize,
-                                line: line.to_string(),
-                                parsed_line_number: line_number,
-                            },
-                        })?
-                        .to_string();
-                    TerminalEvent::Listing(FileSystemListing::File(name, size))
+                    let size = size
+                        .parse::<u64>()
+                        .map_err(|_| parse_error("invalid file size"))?;
+                    TerminalEvent::Listing(FileSystemListing::File(name.to_string(), size))
                 } else {
-                    panic!("Unable to parse terminal replay of line: {line}");
+                    return Err(parse_error("unrecognized terminal output"));
                 }
             };
 
@@ -4,7 +4,11 @@
 
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail, Error};
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle, Solution},
+};
 
 #[cfg(test)]
 mod tests {
@@ -47,20 +51,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn from_str_should_report_the_line_with_an_invalid_tree() {
+        let input = "
+303
+2x5
+";
+
+        let Err(error) = Forest::from_str(input) else {
+            panic!("Expected an error");
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "day 8, line 3: invalid tree height 'x': \"2x5\""
+        );
+    }
 }
 
 /// Part 1 of Day 8.
 pub mod part1 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
     /// Solution for part 1 day 8.
     pub fn solution() -> Result<(), Error> {
-        let filename = current_dir()?.join("src/data/day8.txt");
-        let input = read_to_string(filename)?;
+        let input = InputSource::default().read(8)?;
 
-        let forest = Forest::from_str(&input)?;
-        let answer = forest.get_num_visible_trees_from_outside();
+        let answer = Day8.part1(&input)?;
 
         println!("Day 8 Part 1 = {answer}");
 
@@ -71,15 +89,12 @@ pub mod part1 {
 /// Part 2 of Day 8.
 pub mod part2 {
     use super::*;
-    use std::{env::current_dir, fs::read_to_string};
 
     /// Solution for part 2 day 8.
     pub fn solution() -> Result<(), Error> {
-        let filename = current_dir()?.join("src/data/day8.txt");
-        let input = read_to_string(filename)?;
+        let input = InputSource::default().read(8)?;
 
-        let forest = Forest::from_str(&input)?;
-        let answer = forest.get_highest_scenic_score()?;
+        let answer = Day8.part2(&input)?;
 
         println!("Day 8 Part 2 = {answer}");
 
@@ -87,6 +102,25 @@ pub mod part2 {
     }
 }
 
+/// Treetop Tree House.
+pub struct Day8;
+
+impl Puzzle for Day8 {
+    type Input<'a> = Forest;
+
+    fn parse(&self, input: &str) -> Result<Forest, Error> {
+        Forest::from_str(input)
+    }
+
+    fn solve_part1(&self, forest: &Forest) -> Result<Answer, Error> {
+        Ok(forest.get_num_visible_trees_from_outside().into())
+    }
+
+    fn solve_part2(&self, forest: &Forest) -> Result<Answer, Error> {
+        Ok(forest.get_highest_scenic_score()?.into())
+    }
+}
+
 /// Represents a tree in the Forest.
 pub struct Tree {
     /// The height of the tree.
@@ -120,7 +154,7 @@ impl FromStr for Forest {
         let mut heights = vec![];
         let mut forest_width = 0usize;
 
-        for line in input.lines() {
+        for (line_number, line) in (1..).zip(input.lines()) {
             if line.trim().is_empty() {
                 continue;
             }
@@ -139,14 +173,28 @@ impl FromStr for Forest {
                     '7' => 7u32,
                     '8' => 8u32,
                     '9' => 9u32,
-                    _ => bail!("Forest parse error: invalid character in input: {c}"),
+                    _ => {
+                        return Err(Error::parse(
+                            8,
+                            line_number,
+                            line,
+                            format!("invalid tree height {c:?}"),
+                        ))
+                    }
                 };
 
                 row.push(height);
             }
 
-            if row.len() > forest_width {
+            if heights.is_empty() {
                 forest_width = row.len();
+            } else if row.len() != forest_width {
+                return Err(Error::parse(
+                    8,
+                    line_number,
+                    line,
+                    format!("expected a row of {forest_width} trees"),
+                ));
             }
 
             heights.push(row);
@@ -162,13 +210,15 @@ impl FromStr for Forest {
             for row_index in 0..forest_height {
                 let h = heights
                     .get(row_index)
-                    .ok_or_else(|| anyhow!("Invalid row: ({})", row_index,))?
+                    .ok_or_else(|| Error::unsolvable(8, format!("Invalid row: ({})", row_index)))?
                     .get(col_index)
                     .ok_or_else(|| {
-                        anyhow!(
-                            "Invalid row, col coordinate: ({}, {})",
-                            row_index,
-                            col_index
+                        Error::unsolvable(
+                            8,
+                            format!(
+                                "Invalid row, col coordinate: ({}, {})",
+                                row_index, col_index
+                            ),
                         )
                     })?;
                 col.push(h);
@@ -266,7 +316,6 @@ impl Forest {
                 true => 1,
                 false => 0,
             })
-            .into_iter()
             .sum()
     }
 
@@ -276,6 +325,6 @@ impl Forest {
             .iter()
             .map(|t| t.scenic_score)
             .max()
-            .ok_or_else(|| anyhow!("Error getting the highest scneic score."))
+            .ok_or_else(|| Error::unsolvable(8, "the forest has no trees"))
     }
 }
@@ -8,7 +8,11 @@ use std::{
     ops::{Add, Sub},
 };
 
-use anyhow::{anyhow, bail, Error};
+use crate::{
+    error::Error,
+    input::InputSource,
+    solution::{Answer, Puzzle},
+};
 
 #[cfg(test)]
 mod tests {
@@ -85,18 +89,32 @@ U 20
 
         Ok(())
     }
+
+    #[test]
+    fn parse_reports_the_line_with_an_invalid_direction() {
+        let input = "
+R 4
+X 4
+";
+
+        let Err(error) = Rope::parse(input, 2) else {
+            panic!("Expected an error");
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "day 9, line 3: invalid rope motion direction: \"X 4\""
+        );
+    }
 }
 
 /// Part 1.
 pub mod part1 {
-    use std::{env::current_dir, fs::read_to_string};
-
     use super::*;
 
     /// Solution for Part 1.
     pub fn solution() -> Result<(), Error> {
-        let filename = current_dir()?.join("src/data/day9.txt");
-        let input = read_to_string(filename)?;
+        let input = InputSource::default().read(9)?;
 
         let rope = Rope::parse(&input, 2)?;
 
@@ -111,14 +129,11 @@ pub mod part1 {
 
 /// Part 2.
 pub mod part2 {
-    use std::{env::current_dir, fs::read_to_string};
-
     use super::*;
 
     /// Solution for Part 2.
     pub fn solution() -> Result<(), Error> {
-        let filename = current_dir()?.join("src/data/day9.txt");
-        let input = read_to_string(filename)?;
+        let input = InputSource::default().read(9)?;
 
         let rope = Rope::parse(&input, 10)?;
 
@@ -131,6 +146,33 @@ pub mod part2 {
     }
 }
 
+/// Rope Bridge.
+pub struct Day9;
+
+impl Puzzle for Day9 {
+    type Input<'a> = Vec<Motion>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Motion>, Error> {
+        Motion::parse_all(input)
+    }
+
+    fn solve_part1(&self, motions: &Vec<Motion>) -> Result<Answer, Error> {
+        let rope = Rope::simulate(motions, 2)?;
+
+        Ok(rope
+            .get_number_of_positions_rope_tail_visits_at_least_once()
+            .into())
+    }
+
+    fn solve_part2(&self, motions: &Vec<Motion>) -> Result<Answer, Error> {
+        let rope = Rope::simulate(motions, 10)?;
+
+        Ok(rope
+            .get_number_of_positions_rope_tail_visits_at_least_once()
+            .into())
+    }
+}
+
 /// Representation of a position.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Position {
@@ -171,6 +213,58 @@ impl Position {
     }
 }
 
+/// A motion of the head of the rope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    /// The direction the head moves in: `R`, `L`, `U` or `D`.
+    direction: &'static str,
+
+    /// The number of steps the head moves.
+    num_moves: i32,
+}
+
+impl Motion {
+    /// Parses the motions of the head of the rope from string input.
+    pub fn parse_all(s: &str) -> Result<Vec<Self>, Error> {
+        let mut motions = vec![];
+
+        for (line_number, line) in (1..).zip(s.lines()) {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let parse_error = |reason| Error::parse(9, line_number, line, reason);
+
+            let parts = line.split(' ').collect::<Vec<&str>>();
+            if parts.len() < 2 {
+                return Err(parse_error("rope motion is missing parameters"));
+            }
+
+            let direction = match parts.first() {
+                Some(&"R") => "R",
+                Some(&"L") => "L",
+                Some(&"U") => "U",
+                Some(&"D") => "D",
+                _ => return Err(parse_error("invalid rope motion direction")),
+            };
+
+            let num_moves = parts
+                .get(1)
+                .ok_or_else(|| parse_error("rope motion is missing its number of moves"))?
+                .parse::<i32>()
+                .map_err(|_| parse_error("invalid number of moves"))?;
+
+            motions.push(Motion {
+                direction,
+                num_moves,
+            });
+        }
+
+        Ok(motions)
+    }
+}
+
 /// Representation of Rope.
 #[derive(Debug)]
 pub struct Rope {
@@ -184,7 +278,7 @@ impl Rope {
         let mut updated_knots = Vec::with_capacity(knots.len());
 
         let Some(&start_position) = knots.first() else {
-            bail!("No head knot!");
+            return Err(Error::unsolvable(9, "No head knot!"));
         };
 
         let mut head_position = start_position;
@@ -207,7 +301,12 @@ impl Rope {
                 x: head_position.x,
                 y: head_position.y - 1,
             },
-            _ => bail!("Invalid direction! This should never happen..."),
+            _ => {
+                return Err(Error::unsolvable(
+                    9,
+                    "Invalid direction! This should never happen...",
+                ))
+            }
         };
 
         updated_knots.insert(0, head_position);
@@ -281,62 +380,58 @@ impl Rope {
 
     /// Parses string input and simulates the rope's motions.
     fn parse(s: &str, rope_length: usize) -> Result<Self, Error> {
+        Self::simulate(&Motion::parse_all(s)?, rope_length)
+    }
+
+    /// Simulates the rope's motions.
+    ///
+    /// # Arguments
+    ///
+    /// * `motions` - The motions of the head of the rope.
+    /// * `rope_length` - The number of knots in the rope, including the head and the tail.
+    pub fn simulate(motions: &[Motion], rope_length: usize) -> Result<Self, Error> {
         if rope_length < 2 {
-            bail!(
-                "Rope has to have a length of at least 2! {} requested.",
-                rope_length
-            );
+            return Err(Error::unsolvable(
+                9,
+                format!(
+                    "Rope has to have a length of at least 2! {} requested.",
+                    rope_length
+                ),
+            ));
         }
 
         let knots = vec![Position { x: 0, y: 0 }; rope_length];
 
         let Some(&tail_position) = knots.last() else {
-            bail!("No tail knot found!");
+            return Err(Error::unsolvable(9, "No tail knot found!"));
         };
 
         let mut tail_positions_visited = vec![tail_position];
         let mut updated_knots = knots;
 
-        for line in s.lines() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
-
-            let parts = line.split(' ').collect::<Vec<&str>>();
-            if parts.len() < 2 {
-                bail!("Rope motion is missing parameters: {line}");
-            }
-
-            match parts.first() {
-                Some(direction @ &"R")
-                | Some(direction @ &"L")
-                | Some(direction @ &"U")
-                | Some(direction @ &"D") => {
-                    let num_moves = parts
-                        .get(1)
-                        .ok_or_else(|| anyhow!("Error getting number of moves in line: {line}"))?
-                        .parse::<i32>()?;
-
-                    // Process the offset one by one, because the path the tail takes depends on
-                    // each individual movement of the head, NOT its final position.
-                    for i in 0..num_moves {
-                        let Some(&prev_tail) = updated_knots.last() else {
-                            bail!("Unable to grab current tail at beginning of move #{}", i);
-                        };
+        for motion in motions {
+            // Process the offset one by one, because the path the tail takes depends on
+            // each individual movement of the head, NOT its final position.
+            for i in 0..motion.num_moves {
+                let Some(&prev_tail) = updated_knots.last() else {
+                    return Err(Error::unsolvable(
+                        9,
+                        format!("Unable to grab current tail at beginning of move #{}", i),
+                    ));
+                };
 
-                        updated_knots = Self::get_updated_positions(direction, &updated_knots)?;
+                updated_knots = Self::get_updated_positions(motion.direction, &updated_knots)?;
 
-                        let Some(&new_tail) = updated_knots.last() else {
-                            bail!("Unable to grab current tail at end of move #{}", i);
-                        };
+                let Some(&new_tail) = updated_knots.last() else {
+                    return Err(Error::unsolvable(
+                        9,
+                        format!("Unable to grab current tail at end of move #{}", i),
+                    ));
+                };
 
-                        if new_tail != prev_tail {
-                            tail_positions_visited.push(new_tail);
-                        }
-                    }
+                if new_tail != prev_tail {
+                    tail_positions_visited.push(new_tail);
                 }
-                _ => bail!("Invalid rope motion direction: {line}"),
             }
         }
 
@@ -362,17 +457,13 @@ impl Display for Rope {
             .copied()
             .collect::<HashSet<Position>>();
 
-        let (lower_bound, upper_bound, origin) = unique_positions.iter().fold(
-            Ok((
+        let (lower_bound, upper_bound, origin) = unique_positions.iter().try_fold(
+            (
                 Position { x: 0, y: 0 },
                 Position { x: 0, y: 0 },
                 Position { x: 0, y: 0 },
-            )),
-            |r, p| {
-                let Ok((l, u, o)) = r else {
-                    return r;
-                };
-
+            ),
+            |(l, u, o), p| {
                 let mut origin = o;
 
                 if p.x < 0 {
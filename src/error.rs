@@ -0,0 +1,145 @@
+//! Errors shared by every day's solutions.
+
+use std::{fmt::Display, io, path::PathBuf};
+
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_displays_the_day_line_number_and_offending_text() {
+        let error = Error::parse(4, 3, "2-4;6-8", "expected two comma separated ranges");
+
+        assert_eq!(
+            error.to_string(),
+            "day 4, line 3: expected two comma separated ranges: \"2-4;6-8\""
+        );
+    }
+
+    #[test]
+    fn input_error_displays_the_path_it_tried() {
+        let error = Error::Input {
+            day: 7,
+            path: Some(PathBuf::from("does/not/exist.txt")),
+            source: io::Error::from(io::ErrorKind::NotFound),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "unable to read day 7 input from does/not/exist.txt"
+        );
+    }
+}
+
+/// An error produced while reading, parsing or solving a puzzle.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// A line of puzzle input could not be parsed.
+    #[error("day {day}, line {line_number}: {reason}: {line:?}")]
+    Parse {
+        /// The day whose input was being parsed.
+        day: u8,
+
+        /// The line number (starting at 1) of the offending line.
+        line_number: usize,
+
+        /// The offending line.
+        line: String,
+
+        /// Why the line could not be parsed.
+        reason: String,
+    },
+
+    /// The puzzle input could not be read.
+    #[error("unable to read day {day} input from {}", describe_path(path))]
+    Input {
+        /// The day whose input was being read.
+        day: u8,
+
+        /// The file that was read, or `None` for standard input.
+        path: Option<PathBuf>,
+
+        /// The underlying I/O error.
+        source: io::Error,
+    },
+
+    /// The puzzle input could not be read part way through.
+    #[error("day {day}, line {line_number}: unable to read input")]
+    Read {
+        /// The day whose input was being read.
+        day: u8,
+
+        /// The line number (starting at 1) of the line that could not be read.
+        line_number: usize,
+
+        /// The underlying I/O error.
+        source: io::Error,
+    },
+
+    /// The puzzle input was parsed, but it does not have an answer.
+    #[error("day {day}: {reason}")]
+    Unsolvable {
+        /// The day being solved.
+        day: u8,
+
+        /// Why there is no answer.
+        reason: String,
+    },
+
+    /// The file of known answers could not be read or parsed.
+    #[error("unable to load known answers from {}: {reason}", path.display())]
+    KnownAnswers {
+        /// The file of known answers.
+        path: PathBuf,
+
+        /// Why the known answers could not be loaded.
+        reason: String,
+    },
+
+    /// There is no solution for the requested day and part.
+    #[error("no solution for day {day} part {part}")]
+    UnknownSolution {
+        /// The requested day.
+        day: u8,
+
+        /// The requested part.
+        part: u8,
+    },
+}
+
+impl Error {
+    /// Creates a [`Error::Parse`] error.
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - The day whose input was being parsed.
+    /// * `line_number` - The line number (starting at 1) of the offending line.
+    /// * `line` - The offending line.
+    /// * `reason` - Why the line could not be parsed.
+    pub fn parse(day: u8, line_number: usize, line: &str, reason: impl Display) -> Self {
+        Error::Parse {
+            day,
+            line_number,
+            line: line.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Creates an [`Error::Unsolvable`] error.
+    pub fn unsolvable(day: u8, reason: impl Display) -> Self {
+        Error::Unsolvable {
+            day,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Describes where input was read from.
+fn describe_path(path: &Option<PathBuf>) -> String {
+    match path {
+        Some(path) => path.display().to_string(),
+        None => "stdin".to_string(),
+    }
+}
@@ -0,0 +1,121 @@
+//! Loading puzzle input.
+
+use std::{
+    env,
+    ffi::OsString,
+    fs::read_to_string,
+    io::{stdin, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::error::Error;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_prefers_an_explicit_path() {
+        let source = InputSource::from_arg("my/input.txt");
+
+        assert_eq!(
+            source.resolve_with(7, Some("elsewhere".into())),
+            Some(PathBuf::from("my/input.txt"))
+        );
+    }
+
+    #[test]
+    fn resolve_returns_nothing_for_stdin() {
+        let source = InputSource::from_arg("-");
+
+        assert_eq!(source, InputSource::Stdin);
+        assert_eq!(source.resolve_with(7, Some("elsewhere".into())), None);
+    }
+
+    #[test]
+    fn resolve_uses_the_input_directory_before_the_crate_data() {
+        let source = InputSource::Default;
+
+        assert_eq!(
+            source.resolve_with(7, Some("elsewhere".into())),
+            Some(PathBuf::from("elsewhere/day7.txt"))
+        );
+        assert_eq!(
+            source.resolve_with(7, None),
+            Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("src/data/day7.txt"))
+        );
+    }
+
+    #[test]
+    fn read_names_the_path_it_tried() {
+        let source = InputSource::from_arg("does/not/exist.txt");
+
+        let error = source.read(7).unwrap_err();
+
+        assert!(error.to_string().contains("does/not/exist.txt"));
+    }
+}
+
+/// The environment variable naming a directory that holds `dayN.txt` input files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// An explicit file.
+    File(PathBuf),
+
+    /// Standard input.
+    Stdin,
+
+    /// `dayN.txt` in the directory named by [`INPUT_DIR_VAR`] when it is set, otherwise
+    /// `src/data/dayN.txt` in this crate.
+    #[default]
+    Default,
+}
+
+impl InputSource {
+    /// Creates an input source from a command line argument, where `-` means standard input.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Gets the path of the file to read for the given day, or `None` for standard input.
+    pub fn resolve(&self, day: u8) -> Option<PathBuf> {
+        self.resolve_with(day, env::var_os(INPUT_DIR_VAR))
+    }
+
+    /// Reads the puzzle input for the given day.
+    pub fn read(&self, day: u8) -> Result<String, Error> {
+        let path = self.resolve(day);
+
+        let result = match &path {
+            Some(path) => read_to_string(path),
+            None => {
+                let mut input = String::new();
+                stdin().read_to_string(&mut input).map(|_| input)
+            }
+        };
+
+        result.map_err(|source| Error::Input { day, path, source })
+    }
+
+    /// Resolves the path for the given day using `input_dir` in place of [`INPUT_DIR_VAR`].
+    fn resolve_with(&self, day: u8, input_dir: Option<OsString>) -> Option<PathBuf> {
+        let filename = format!("day{day}.txt");
+
+        match self {
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+            InputSource::Default => Some(match input_dir {
+                Some(dir) => PathBuf::from(dir).join(filename),
+                None => Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("src/data")
+                    .join(filename),
+            }),
+        }
+    }
+}
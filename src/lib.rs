@@ -1,3 +1,12 @@
+//! Advent of Code 2022 solutions.
+
+use std::ops::RangeInclusive;
+
+use error::Error;
+use solution::{Answer, Solution};
+use timing::{Timed, Timing};
+
+pub mod answers;
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -9,3 +18,64 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+pub mod error;
+pub mod input;
+pub mod solution;
+pub mod timing;
+
+/// The days that have solutions.
+pub const DAYS: RangeInclusive<u8> = 1..=11;
+
+/// The parts that each day has.
+pub const PARTS: RangeInclusive<u8> = 1..=2;
+
+/// Gets the solution for the given day, if there is one.
+pub fn solution(day: u8) -> Option<&'static dyn Solution> {
+    Some(match day {
+        1 => &day1::Day1,
+        2 => &day2::Day2,
+        3 => &day3::Day3,
+        4 => &day4::Day4,
+        5 => &day5::Day5,
+        6 => &day6::Day6,
+        7 => &day7::Day7,
+        8 => &day8::Day8,
+        9 => &day9::Day9,
+        10 => &day10::Day10,
+        11 => &day11::Day11,
+        _ => return None,
+    })
+}
+
+/// Runs the solution for the given day and part.
+///
+/// # Arguments
+///
+/// * `day` - The day to run, e.g. `7`.
+/// * `part` - The part of the day to run, either `1` or `2`.
+/// * `input` - The puzzle input for the day.
+pub fn run(day: u8, part: u8, input: &str) -> Result<Answer, Error> {
+    match (solution(day), part) {
+        (Some(solution), 1) => solution.part1(input),
+        (Some(solution), 2) => solution.part2(input),
+        _ => Err(Error::UnknownSolution { day, part }),
+    }
+}
+
+/// Runs the solution for the given day and part, timing the parse and the solve separately.
+///
+/// # Arguments
+///
+/// * `day` - The day to run, e.g. `7`.
+/// * `part` - The part of the day to run, either `1` or `2`.
+/// * `input` - The puzzle input for the day.
+pub fn run_timed(day: u8, part: u8, input: &str) -> Timed {
+    match (solution(day), part) {
+        (Some(solution), 1) => solution.part1_timed(input),
+        (Some(solution), 2) => solution.part2_timed(input),
+        _ => Timed {
+            answer: Err(Error::UnknownSolution { day, part }),
+            timing: Timing::default(),
+        },
+    }
+}
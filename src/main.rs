@@ -1,8 +1,333 @@
-use advent_of_code_2022::day11;
+mod cli;
 
-use anyhow::Error;
+use std::{
+    env,
+    fmt::Display,
+    io::{self, IsTerminal},
+};
+
+use advent_of_code_2022::{
+    answers::{KnownAnswers, Verdict},
+    day5::{self, Crane},
+    error::Error as SolutionError,
+    input::InputSource,
+    run_timed, solution,
+    solution::Answer,
+    timing::{self, Summary, Timed, Timing},
+    PARTS,
+};
+use anyhow::{bail, Error};
+use cli::{
+    AnimateOptions, BenchOptions, Command, Format, RunOptions, Selection, VerifyOptions, USAGE,
+};
+use serde::Serialize;
 
 fn main() -> Result<(), Error> {
-    day11::part2::solution()?;
+    let command = Command::parse(env::args().skip(1))?;
+
+    // Reject unknown days and parts before reading any input, which may block on stdin.
+    if let Command::Run(RunOptions { selection, .. })
+    | Command::Bench(BenchOptions { selection, .. }) = &command
+    {
+        check_selection(selection)?;
+    }
+
+    match command {
+        Command::Help => println!("{USAGE}"),
+        Command::Run(RunOptions {
+            selection:
+                Selection::Day {
+                    day,
+                    part: Some(part),
+                },
+            input,
+            format: Format::Text,
+        }) => {
+            let timed = run_timed(day, part, &input.read(day)?);
+            println!("{}", timed.answer?);
+            // Keep the answer alone on stdout, so that it can be piped elsewhere.
+            eprintln!("{}", describe_timing(&timed.timing));
+        }
+        Command::Run(options) => run_all(&options)?,
+        Command::Verify(options) => verify(&options)?,
+        Command::Bench(options) => bench(&options)?,
+        Command::Animate(options) => animate(&options)?,
+    }
+
+    Ok(())
+}
+
+/// Checks that there are solutions for the selected day and part.
+fn check_selection(selection: &Selection) -> Result<(), Error> {
+    match *selection {
+        Selection::Day {
+            day,
+            part: Some(part),
+        } if solution(day).is_none() || !PARTS.contains(&part) => {
+            Err(SolutionError::UnknownSolution { day, part }.into())
+        }
+        Selection::Day { day, part: None } if solution(day).is_none() => {
+            bail!("No solutions for day {day}.")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Runs the selected solutions, reporting failures without stopping.
+fn run_all(options: &RunOptions) -> Result<(), Error> {
+    let mut num_failures = 0;
+    let parts = options.selection.parts();
+
+    for day in options.selection.days() {
+        // Read the input once per day so that both parts can share stdin.
+        let input = match options.input.read(day) {
+            Ok(input) => input,
+            Err(e) => {
+                match options.format {
+                    Format::Text => eprintln!("Day {day} failed: {e:#}"),
+                    Format::Json => {
+                        for &part in &parts {
+                            print_json(&Report::failed(day, part, &e))?;
+                        }
+                    }
+                }
+                num_failures += parts.len();
+                continue;
+            }
+        };
+
+        for &part in &parts {
+            let timed = run_timed(day, part, &input);
+
+            if timed.answer.is_err() {
+                num_failures += 1;
+            }
+
+            match options.format {
+                Format::Text => {
+                    println!(
+                        "=== Day {day} Part {part} ({}) ===",
+                        describe_timing(&timed.timing)
+                    );
+                    match timed.answer {
+                        Ok(answer) => println!("{answer}"),
+                        Err(e) => eprintln!("Day {day} Part {part} failed: {e:#}"),
+                    }
+                }
+                Format::Json => print_json(&Report::new(day, part, &timed))?,
+            }
+        }
+    }
+
+    if num_failures > 0 {
+        bail!("{num_failures} solution(s) failed.");
+    }
+
+    Ok(())
+}
+
+/// The result of running one part of a puzzle, as printed by `run --format json`.
+#[derive(Serialize)]
+struct Report<'a> {
+    day: u8,
+    part: u8,
+    answer: Option<&'a Answer>,
+    timing: Option<TimingReport>,
+    error: Option<String>,
+}
+
+/// How long it took to produce an answer, in nanoseconds.
+#[derive(Serialize)]
+struct TimingReport {
+    parse_ns: u128,
+    solve_ns: u128,
+}
+
+impl<'a> Report<'a> {
+    /// Reports a solution that was run.
+    fn new(day: u8, part: u8, timed: &'a Timed) -> Self {
+        Report {
+            day,
+            part,
+            answer: timed.answer.as_ref().ok(),
+            timing: Some(TimingReport {
+                parse_ns: timed.timing.parse.as_nanos(),
+                solve_ns: timed.timing.solve.as_nanos(),
+            }),
+            error: timed.answer.as_ref().err().map(ToString::to_string),
+        }
+    }
+
+    /// Reports a solution that could not be run.
+    fn failed(day: u8, part: u8, error: &impl Display) -> Self {
+        Report {
+            day,
+            part,
+            answer: None,
+            timing: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// Prints a value as a single line of JSON.
+fn print_json(value: &impl Serialize) -> Result<(), Error> {
+    println!("{}", serde_json::to_string(value)?);
+
+    Ok(())
+}
+
+/// Checks the selected solutions against their known answers, printing a table of the results.
+fn verify(options: &VerifyOptions) -> Result<(), Error> {
+    let known_answers = KnownAnswers::load(&options.answers)?;
+    let use_color = io::stdout().is_terminal();
+    let mut num_checks = 0;
+    let mut num_failures = 0;
+
+    println!("Day  Part  Result   Answer");
+
+    for day in options.selection.days() {
+        let input = InputSource::Default.read(day).map_err(|e| e.to_string());
+
+        for part in options.selection.parts() {
+            let verdict = input
+                .as_deref()
+                .map(|input| known_answers.verify(day, part, input));
+
+            let mut mismatch = None;
+            let (result, color, details) = match verdict {
+                Ok(Verdict::Pass(answer)) => ("PASS", GREEN, summarize(&answer)),
+                Ok(Verdict::Fail { expected, actual }) => {
+                    let details = format!(
+                        "expected {}, got {}",
+                        summarize(&expected),
+                        summarize(&actual)
+                    );
+                    mismatch = Some((expected, actual));
+                    ("FAIL", RED, details)
+                }
+                Ok(Verdict::Unknown(answer)) => (
+                    "MISSING",
+                    YELLOW,
+                    format!("{} (no known answer)", summarize(&answer)),
+                ),
+                Ok(Verdict::Error(e)) => ("ERROR", RED, e.to_string()),
+                Err(e) => ("ERROR", RED, e.clone()),
+            };
+
+            let passed = result == "PASS";
+            let result = format!("{result:<7}");
+            let result = if use_color {
+                format!("{color}{result}{RESET}")
+            } else {
+                result
+            };
+            println!("{day:>3}  {part:>4}  {result}  {details}");
+
+            // CRT answers are too big for the table, so show the whole drawing when they differ.
+            if let Some((expected @ Answer::Crt(_), actual)) = &mismatch {
+                println!("Expected:\n{expected}\nGot:\n{actual}");
+            }
+
+            num_checks += 1;
+            if !passed {
+                num_failures += 1;
+            }
+        }
+    }
+
+    if num_failures > 0 {
+        bail!("{num_failures} of {num_checks} check(s) failed.");
+    }
+
     Ok(())
 }
+
+/// Times repeated runs of the selected solutions, printing a table of the results.
+fn bench(options: &BenchOptions) -> Result<(), Error> {
+    let mut num_failures = 0;
+
+    println!(
+        "Day  Part  {:<32}  {:<32}",
+        "Parse (min / median / max)", "Solve (min / median / max)"
+    );
+
+    for day in options.selection.days() {
+        let input = match options.input.read(day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {day} failed: {e:#}");
+                num_failures += options.selection.parts().len();
+                continue;
+            }
+        };
+
+        for part in options.selection.parts() {
+            match timing::bench(day, part, &input, options.runs) {
+                Ok(bench) => println!(
+                    "{day:>3}  {part:>4}  {:<32}  {:<32}",
+                    describe_summary(&bench.parse),
+                    describe_summary(&bench.solve)
+                ),
+                Err(e) => {
+                    eprintln!("Day {day} Part {part} failed: {e:#}");
+                    num_failures += 1;
+                }
+            }
+        }
+    }
+
+    if num_failures > 0 {
+        bail!("{num_failures} solution(s) failed.");
+    }
+
+    Ok(())
+}
+
+/// Animates the day 5 crates being rearranged by the selected part's crane.
+fn animate(options: &AnimateOptions) -> Result<(), Error> {
+    let input = options.input.read(5)?;
+    let (crates, instructions) = day5::parse_initial_state_and_instructions(&input)?;
+    let crane = match options.part {
+        1 => Crane::CRATE_MOVER_9000,
+        _ => Crane::CRATE_MOVER_9001,
+    };
+
+    day5::animate(
+        &crates,
+        &instructions,
+        &crane,
+        &options.animation,
+        &mut io::stdout().lock(),
+    )?;
+
+    Ok(())
+}
+
+/// Describes how long it took to produce an answer.
+fn describe_timing(timing: &Timing) -> String {
+    format!("parse: {:.2?}, solve: {:.2?}", timing.parse, timing.solve)
+}
+
+/// Describes the spread of a set of timing samples.
+fn describe_summary(summary: &Summary) -> String {
+    format!(
+        "{:.2?} / {:.2?} / {:.2?}",
+        summary.min, summary.median, summary.max
+    )
+}
+
+/// ANSI escape codes for coloring the verify table.
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// Summarizes an answer on a single line, so that it fits in a table row.
+fn summarize(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(s) => format!("{s:?}"),
+        Answer::Crt(rows) => format!("CRT output ({} rows)", rows.len()),
+    }
+}
@@ -0,0 +1,213 @@
+//! The interface shared by every day's solutions.
+
+use std::{fmt::Display, time::Instant};
+
+use serde::Serialize;
+
+use crate::{
+    error::Error,
+    timing::{Timed, Timing},
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_displays_crt_output_one_row_per_line() {
+        let answer = Answer::from_crt("#..#\n.##.");
+
+        assert_eq!(answer.to_string(), "#..#\n.##.");
+    }
+
+    #[test]
+    fn answer_from_holds_any_integer_answer() {
+        assert_eq!(Answer::from(-1i32), Answer::Number(-1));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+    }
+
+    #[test]
+    fn answer_serializes_as_its_natural_json_type() -> Result<(), serde_json::Error> {
+        assert_eq!(serde_json::to_string(&Answer::Number(24000))?, "24000");
+        assert_eq!(
+            serde_json::to_string(&Answer::Text("CMZ".to_string()))?,
+            "\"CMZ\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Answer::from_crt("#..#\n.##."))?,
+            "[\"#..#\",\".##.\"]"
+        );
+
+        Ok(())
+    }
+
+    struct Doubler;
+
+    impl Puzzle for Doubler {
+        type Input<'a> = i32;
+
+        fn parse(&self, input: &str) -> Result<i32, Error> {
+            input
+                .trim()
+                .parse()
+                .map_err(|e| Error::parse(0, 1, input, e))
+        }
+
+        fn solve_part1(&self, input: &i32) -> Result<Answer, Error> {
+            Ok((input * 2).into())
+        }
+
+        fn solve_part2(&self, input: &i32) -> Result<Answer, Error> {
+            Ok((input * 4).into())
+        }
+    }
+
+    #[test]
+    fn every_puzzle_is_a_solution() -> Result<(), Error> {
+        assert_eq!(Doubler.part1("21")?, Answer::Number(42));
+        assert_eq!(Doubler.part2_timed("21").answer?, Answer::Number(84));
+        assert!(Doubler.part1_timed("twenty one").answer.is_err());
+
+        Ok(())
+    }
+}
+
+/// A solution to both parts of a day's puzzle.
+///
+/// This is implemented for every [`Puzzle`], and can be used without knowing the day's parsed
+/// input type.
+pub trait Solution {
+    /// Solves part 1 for the given puzzle input.
+    fn part1(&self, input: &str) -> Result<Answer, Error>;
+
+    /// Solves part 2 for the given puzzle input.
+    fn part2(&self, input: &str) -> Result<Answer, Error>;
+
+    /// Solves part 1 for the given puzzle input, timing the parse and the solve separately.
+    fn part1_timed(&self, input: &str) -> Timed;
+
+    /// Solves part 2 for the given puzzle input, timing the parse and the solve separately.
+    fn part2_timed(&self, input: &str) -> Timed;
+}
+
+/// A day's puzzle, split into parsing the input and solving each part of it.
+pub trait Puzzle {
+    /// The parsed puzzle input.
+    type Input<'a>;
+
+    /// Parses the puzzle input, ready to solve either part.
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, Error>;
+
+    /// Solves part 1 for the parsed puzzle input.
+    fn solve_part1(&self, input: &Self::Input<'_>) -> Result<Answer, Error>;
+
+    /// Solves part 2 for the parsed puzzle input.
+    fn solve_part2(&self, input: &Self::Input<'_>) -> Result<Answer, Error>;
+}
+
+impl<P: Puzzle> Solution for P {
+    fn part1(&self, input: &str) -> Result<Answer, Error> {
+        self.solve_part1(&self.parse(input)?)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Error> {
+        self.solve_part2(&self.parse(input)?)
+    }
+
+    fn part1_timed(&self, input: &str) -> Timed {
+        time(self, input, P::solve_part1)
+    }
+
+    fn part2_timed(&self, input: &str) -> Timed {
+        time(self, input, P::solve_part2)
+    }
+}
+
+/// Parses the input and solves one part of a puzzle, timing each step.
+fn time<P: Puzzle>(
+    puzzle: &P,
+    input: &str,
+    solve: impl Fn(&P, &P::Input<'_>) -> Result<Answer, Error>,
+) -> Timed {
+    let start = Instant::now();
+    let parsed = puzzle.parse(input);
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = parsed.and_then(|parsed| solve(puzzle, &parsed));
+    let solve = start.elapsed();
+
+    Timed {
+        answer,
+        timing: Timing { parse, solve },
+    }
+}
+
+/// The answer to one part of a puzzle.
+///
+/// Answers serialize as their natural JSON type: a number, a string, or an array of CRT rows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    /// A numeric answer, e.g. the day 1 calorie count.
+    Number(i128),
+
+    /// A single line of text, e.g. the day 5 crate message.
+    Text(String),
+
+    /// The rows of pixels drawn on a CRT, e.g. the day 10 part 2 output.
+    Crt(Vec<String>),
+}
+
+impl Answer {
+    /// Creates a CRT answer from multi-line output, one row per line.
+    pub fn from_crt(output: &str) -> Self {
+        Answer::Crt(output.lines().map(String::from).collect())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Crt(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
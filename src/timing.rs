@@ -0,0 +1,139 @@
+//! Timing and benchmarking of solutions.
+
+use std::time::Duration;
+
+use crate::{error::Error, run_timed, solution::Answer};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_of_returns_the_min_median_and_max() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis);
+
+        assert_eq!(
+            Summary::of(&samples),
+            Some(Summary {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            })
+        );
+        assert_eq!(Summary::of(&[]), None);
+    }
+
+    #[test]
+    fn bench_repeats_the_solution() -> Result<(), Error> {
+        let bench = bench(6, 1, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", 3)?;
+
+        assert_eq!(bench.answer, Answer::Number(7));
+        assert!(bench.parse.min <= bench.parse.max);
+        assert!(bench.solve.min <= bench.solve.median);
+
+        Ok(())
+    }
+}
+
+/// How long it took to produce an answer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    /// Time spent parsing the puzzle input.
+    pub parse: Duration,
+
+    /// Time spent solving the parsed puzzle input.
+    pub solve: Duration,
+}
+
+/// The answer to one part of a puzzle, along with how long it took to produce.
+#[derive(Debug)]
+pub struct Timed {
+    /// The answer, or why there isn't one.
+    pub answer: Result<Answer, Error>,
+
+    /// How long it took to produce the answer.
+    pub timing: Timing,
+}
+
+/// The spread of a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    /// The fastest sample.
+    pub min: Duration,
+
+    /// The middle sample.
+    pub median: Duration,
+
+    /// The slowest sample.
+    pub max: Duration,
+}
+
+/// The results of repeatedly running one part of a puzzle.
+#[derive(Debug)]
+pub struct Bench {
+    /// The answer produced by the runs.
+    pub answer: Answer,
+
+    /// The spread of the time spent parsing.
+    pub parse: Summary,
+
+    /// The spread of the time spent solving.
+    pub solve: Summary,
+}
+
+impl Timing {
+    /// The total time spent parsing and solving.
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+impl Summary {
+    /// Summarizes a set of samples, or returns `None` if there are no samples.
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        let mut samples = samples.to_vec();
+        samples.sort();
+
+        Some(Summary {
+            min: *samples.first()?,
+            median: *samples.get(samples.len() / 2)?,
+            max: *samples.last()?,
+        })
+    }
+}
+
+/// Runs the solution for the given day and part repeatedly, timing each run.
+///
+/// # Arguments
+///
+/// * `day` - The day to run, e.g. `7`.
+/// * `part` - The part of the day to run, either `1` or `2`.
+/// * `input` - The puzzle input for the day.
+/// * `runs` - The number of times to run the solution, at least one.
+pub fn bench(day: u8, part: u8, input: &str, runs: usize) -> Result<Bench, Error> {
+    let mut answer = None;
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = Vec::with_capacity(runs);
+
+    for _ in 0..runs.max(1) {
+        let timed = run_timed(day, part, input);
+
+        answer = Some(timed.answer?);
+        parse_samples.push(timed.timing.parse);
+        solve_samples.push(timed.timing.solve);
+    }
+
+    // There is at least one run, so there is always an answer and a summary of the samples.
+    match (
+        answer,
+        Summary::of(&parse_samples),
+        Summary::of(&solve_samples),
+    ) {
+        (Some(answer), Some(parse), Some(solve)) => Ok(Bench {
+            answer,
+            parse,
+            solve,
+        }),
+        _ => Err(Error::UnknownSolution { day, part }),
+    }
+}